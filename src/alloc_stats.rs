@@ -0,0 +1,142 @@
+/*
+ * A global allocator that counts allocations, enabled with the `alloc-stats` feature.
+ * example: `cargo solve 24 --release --features alloc-stats`
+ */
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+// live bytes when counting started, the peak is reported on top of those.
+static BASELINE: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    // a reallocation counts as a new allocation of `new_size` bytes.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak_bytes: usize,
+}
+
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Starts counting from zero.
+pub fn reset() {
+    let live = LIVE.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    BASELINE.store(live, Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+}
+
+/// Counts since the last `reset`.
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+    }
+}
+
+/// Runs `f`, along with its allocation counts when the allocator is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    reset();
+    let result = f();
+    let stats = if is_enabled() { Some(snapshot()) } else { None };
+    (result, stats)
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}B", bytes)
+    } else {
+        format!("{:.2}{}", value, units[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting() {
+        reset();
+        let layout = Layout::from_size_align(4096, 8).unwrap();
+        unsafe {
+            let ptr = CountingAllocator.alloc(layout);
+            let ptr = CountingAllocator.realloc(ptr, layout, 8192);
+            CountingAllocator.dealloc(ptr, Layout::from_size_align(8192, 8).unwrap());
+        }
+
+        let stats = snapshot();
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 4096 + 8192);
+        assert!(stats.peak_bytes >= 8192);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00MiB");
+    }
+}
@@ -3,7 +3,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(parse_sums(input).iter().rev().take(3).sum())
+    let mut sums = parse_sums(input);
+    sums.sort();
+    Some(sums.iter().rev().take(3).sum())
 }
 
 fn parse_sums(input: &str) -> Vec<u32> {
@@ -20,7 +22,7 @@ fn parse_sums(input: &str) -> Vec<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 1);
+    let input = &advent_of_code::read_input(1);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -40,4 +42,21 @@ mod tests {
         let input = advent_of_code::read_file("examples", 1);
         assert_eq!(part_two(&input), Some(45000));
     }
+
+    #[test]
+    fn test_unsorted_elves() {
+        // the three largest sums are not the last three elves.
+        let input = "1000\n\n5000\n\n3000\n\n100\n\n4000\n";
+        assert_eq!(part_two(input), Some(12000));
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_top_three_property() {
+        advent_of_code::generators::check_property(1, 20, |input| {
+            let max = part_one(input).unwrap();
+            let top_three = part_two(input).unwrap();
+            assert!(max <= top_three && top_three <= 3 * max);
+        });
+    }
 }
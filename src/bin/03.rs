@@ -1,22 +1,11 @@
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use advent_of_code::helpers::BitSet;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        parse_input_one(input)
-            .iter()
-            .map(char_to_value)
-            .sum(),
-    )
+    Some(parse_input_one(input).iter().sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(
-        parse_input_two(input)
-            .iter()
-            .map(char_to_value)
-            .sum(),
-    )
+    Some(parse_input_two(input).iter().sum())
 }
 
 fn char_to_value(input: &char) -> u32 {
@@ -30,34 +19,44 @@ fn char_to_value(input: &char) -> u32 {
     }
 }
 
-fn parse_input_one(input: &str) -> Vec<char> {
+// the priorities of the items, which all fit in a u64.
+fn item_set(items: &str) -> BitSet {
+    items.chars().map(|c| char_to_value(&c) as usize).collect()
+}
+
+// priorities of the items found in every one of `groups`.
+fn common_items(groups: &[&str]) -> Vec<u32> {
+    groups
+        .iter()
+        .map(|group| item_set(group))
+        .reduce(|acc, set| acc & set)
+        .unwrap_or_default()
+        .iter()
+        .map(|priority| priority as u32)
+        .collect()
+}
+
+fn parse_input_one(input: &str) -> Vec<u32> {
     input
         .lines()
         .flat_map(|line| {
             let (first, second) = line.split_at(line.chars().count() / 2);
-            let first_set: HashSet<char> = HashSet::from_iter(first.chars());
-            let second_set: HashSet<char> = HashSet::from_iter(second.chars());
-            Vec::from_iter(&first_set & &second_set)
+            common_items(&[first, second])
         })
         .collect::<Vec<_>>()
 }
 
-fn parse_input_two(input: &str) -> Vec<char> {
+fn parse_input_two(input: &str) -> Vec<u32> {
     input
         .lines()
         .collect::<Vec<&str>>()
         .chunks(3)
-        .flat_map(|group| {
-            let first_set: HashSet<char> = HashSet::from_iter(group[0].chars());
-            let second_set: HashSet<char> = HashSet::from_iter(group[1].chars());
-            let third_set: HashSet<char> = HashSet::from_iter(group[2].chars());
-            Vec::from_iter(&(&first_set & &second_set) & &third_set)
-        })
+        .flat_map(common_items)
         .collect()
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 3);
+    let input = &advent_of_code::read_input(3);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -69,14 +68,14 @@ mod tests {
     #[test]
     fn test_parse_input_one() {
         let input = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        assert_eq!(parse_input_one(input), vec!['p']);
+        assert_eq!(parse_input_one(input), vec![char_to_value(&'p')]);
     }
 
     #[test]
     fn test_parse_input_two() {
         let input =
             "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg";
-        assert_eq!(parse_input_two(input), vec!['r']);
+        assert_eq!(parse_input_two(input), vec![char_to_value(&'r')]);
     }
 
     #[test]
@@ -1,3 +1,5 @@
+use advent_of_code::helpers::Interval;
+
 pub fn part_one(input: &str) -> Option<usize> {
     Some(
         parse_input(input)
@@ -16,32 +18,28 @@ pub fn part_two(input: &str) -> Option<usize> {
     )
 }
 
-fn contain((x1, x2): (u32, u32), (y1, y2): (u32, u32)) -> bool {
-    (x1 <= y1 && x2 >= y2) || (y1 <= x1 && y2 >= x2)
-}
+type Sections = Interval<u32>;
 
-fn overlap((x1, x2): (u32, u32), (y1, y2): (u32, u32)) -> bool {
-    !(x1 > y2 || y1 > x2)
+fn contain(x: Sections, y: Sections) -> bool {
+    x.contains_interval(&y) || y.contains_interval(&x)
 }
 
-fn to_int(input: &str) -> u32 {
-    input.parse::<u32>().unwrap()
+fn overlap(x: Sections, y: Sections) -> bool {
+    x.overlaps(&y)
 }
 
-fn parse_input(input: &str) -> Vec<((u32, u32), (u32, u32))> {
+fn parse_input(input: &str) -> Vec<(Sections, Sections)> {
     input
         .lines()
         .map(|line| {
             let (x, y) = line.split_once(',').unwrap();
-            let (x1, x2) = x.split_once('-').unwrap();
-            let (y1, y2) = y.split_once('-').unwrap();
-            ((to_int(x1), to_int(x2)), (to_int(y1), to_int(y2)))
+            (x.parse().unwrap(), y.parse().unwrap())
         })
         .collect()
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 4);
+    let input = &advent_of_code::read_input(4);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -53,7 +51,10 @@ mod tests {
     #[test]
     fn test_part_input() {
         let input = "2-4,6-8";
-        assert_eq!(parse_input(input), vec![((2, 4), (6, 8))]);
+        assert_eq!(
+            parse_input(input),
+            vec![(Interval::new(2, 4), Interval::new(6, 8))]
+        );
     }
 
     #[test]
@@ -67,4 +68,15 @@ mod tests {
         let input = advent_of_code::read_file("examples", 4);
         assert_eq!(part_two(&input), Some(4));
     }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_contain_implies_overlap() {
+        advent_of_code::generators::check_property(4, 50, |input| {
+            for (x, y) in parse_input(input) {
+                assert_eq!(contain(x, y), contain(y, x));
+                assert!(!contain(x, y) || overlap(x, y), "{:?} {:?}", x, y);
+            }
+        });
+    }
 }
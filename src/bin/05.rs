@@ -93,7 +93,7 @@ fn parse_input(input: &str) -> (BoardState, Vec<Instruction>) {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
+    let input = &advent_of_code::read_input(5);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -34,7 +34,7 @@ fn all_different(window: &VecDeque<char>) -> bool {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 6);
+    let input = &advent_of_code::read_input(6);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
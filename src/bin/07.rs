@@ -1,14 +1,15 @@
+use advent_of_code::helpers::parse::{parse_all, uint};
 use std::collections::HashMap;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, commands) = commands_parser(input).unwrap();
+    let commands = parse_all(commands_parser, input).unwrap();
     let fs: FileSystem = build_filesystem(commands);
     let sizes: HashMap<Vec<&str>, u32> = calculate_sizes(fs);
     Some(sizes.values().filter(|value| **value < 100000).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, commands) = commands_parser(input).unwrap();
+    let commands = parse_all(commands_parser, input).unwrap();
     let fs: FileSystem = build_filesystem(commands);
     let sizes: HashMap<Vec<&str>, u32> = calculate_sizes(fs);
     let total: u32 = sizes[&vec!["/"]];
@@ -42,18 +43,18 @@ use crate::Command::*;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{digit1, not_line_ending},
+    character::complete::not_line_ending,
     combinator::{map, opt},
     multi::{many1, separated_list1},
     sequence::{preceded, separated_pair, terminated},
     IResult,
 };
 
-fn commands_parser(input: &str) -> IResult<&str, Vec<Command>> {
+fn commands_parser(input: &str) -> IResult<&str, Vec<Command<'_>>> {
     many1(terminated(alt((cd_parser, ls_parser)), opt(tag("\n"))))(input)
 }
 
-fn cd_parser(input: &str) -> IResult<&str, Command> {
+fn cd_parser(input: &str) -> IResult<&str, Command<'_>> {
     map(
         preceded(
             tag("$ cd "),
@@ -67,19 +68,19 @@ fn cd_parser(input: &str) -> IResult<&str, Command> {
     )(input)
 }
 
-fn ls_parser(input: &str) -> IResult<&str, Command> {
+fn ls_parser(input: &str) -> IResult<&str, Command<'_>> {
     map(
         preceded(tag("$ ls\n"), separated_list1(tag("\n"), ls_entry_parser)),
         Command::Ls,
     )(input)
 }
 
-fn ls_entry_parser(input: &str) -> IResult<&str, FsEntry> {
+fn ls_entry_parser(input: &str) -> IResult<&str, FsEntry<'_>> {
     alt((
         map(preceded(tag("dir "), not_line_ending), FsEntry::Folder),
         map(
-            separated_pair(digit1, tag(" "), not_line_ending),
-            |(file_size, file_name)| FsEntry::File(file_name, file_size.parse().unwrap()),
+            separated_pair(uint, tag(" "), not_line_ending),
+            |(file_size, file_name)| FsEntry::File(file_name, file_size),
         ),
     ))(input)
 }
@@ -106,7 +107,7 @@ fn build_filesystem(commands: Vec<Command>) -> FileSystem {
     result
 }
 
-fn calculate_sizes(fs: FileSystem) -> HashMap<Vec<&str>, u32> {
+fn calculate_sizes(fs: FileSystem<'_>) -> HashMap<Vec<&str>, u32> {
     let mut result: HashMap<Vec<&str>, u32> = HashMap::new();
     let mut all_dirs: Vec<Vec<&str>> = fs.keys().cloned().collect();
     all_dirs.sort_by_key(|e| e.len());
@@ -130,7 +131,7 @@ fn calculate_sizes(fs: FileSystem) -> HashMap<Vec<&str>, u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 7);
+    let input = &advent_of_code::read_input(7);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -1,3 +1,5 @@
+use advent_of_code::differential::Variant;
+use advent_of_code::helpers::Grid;
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
 
@@ -5,8 +7,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let board = parse_input(input);
     // print_board(&board);
 
-    let (n_rows, n_cols) = get_dimensions(&board);
-    let mut visible = vec![vec![0; n_cols]; n_rows];
+    let mut visible = Grid::new(board.width(), board.height(), 0);
     fill_borders(&mut visible);
     // print_board(&visible);
 
@@ -20,8 +21,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let board = parse_input(input);
     // print_board(&board);
 
-    let (n_rows, n_cols) = get_dimensions(&board);
-    let mut scores = vec![vec![0; n_cols]; n_rows];
+    let mut scores = Grid::new(board.width(), board.height(), 0);
 
     fill_scores(&board, &mut scores);
     // print_board(&scores);
@@ -29,112 +29,137 @@ pub fn part_two(input: &str) -> Option<u32> {
     highest_score(&scores)
 }
 
-type Board = Vec<Vec<u32>>;
+// checks every tree against every line of sight, slow but hard to get wrong.
+fn part_one_naive(input: &str) -> Option<u32> {
+    let board = parse_input(input);
 
-fn parse_input(input: &str) -> Board {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as u32)
-                .collect()
+    let visible = board
+        .positions()
+        .filter(|&pos| {
+            lines_of_sight(&board, pos)
+                .iter()
+                .any(|line| line.iter().all(|t| *t < board[pos]))
+        })
+        .count();
+
+    Some(visible as u32)
+}
+
+fn part_two_naive(input: &str) -> Option<u32> {
+    let board = parse_input(input);
+
+    board
+        .positions()
+        .map(|pos| {
+            lines_of_sight(&board, pos)
+                .iter()
+                .map(|line| match line.iter().position(|t| *t >= board[pos]) {
+                    Some(index) => index as u32 + 1,
+                    None => line.len() as u32,
+                })
+                .product()
         })
-        .collect()
+        .max()
 }
 
-fn get_dimensions(board: &Board) -> (usize, usize) {
-    let n_cols = board[0].len();
-    let n_rows = board.len();
-    (n_rows, n_cols)
+// trees seen from (x, y) looking up, down, left and right, nearest first.
+fn lines_of_sight(board: &Board, (x, y): (usize, usize)) -> [Vec<u32>; 4] {
+    [
+        board.column(x).take(y).rev().copied().collect(),
+        board.column(x).skip(y + 1).copied().collect(),
+        board.row(y)[..x].iter().rev().copied().collect(),
+        board.row(y)[(x + 1)..].to_vec(),
+    ]
+}
+
+const PART_ONE_VARIANTS: &[Variant<u32>] =
+    &[("visibility_grid", part_one), ("naive", part_one_naive)];
+const PART_TWO_VARIANTS: &[Variant<u32>] = &[("score_grid", part_two), ("naive", part_two_naive)];
+
+type Board = Grid<u32>;
+
+fn parse_input(input: &str) -> Board {
+    Grid::parse(input, |c| c.to_digit(10).unwrap())
 }
 
 fn _print_board(board: &Board) {
-    for line in board {
-        println!(
-            "{}",
-            line.iter()
-                .fold(String::new(), |acc, e| acc + &e.to_string())
-        );
-    }
-    println!()
+    println!("{}\n", board);
 }
 
-#[allow(clippy::needless_range_loop)]
 fn fill_borders(board: &mut Board) {
-    let (n_rows, n_cols) = get_dimensions(board);
+    let (n_rows, n_cols) = (board.height(), board.width());
 
-    for x in 0..n_rows {
-        board[0][x] = 1;
-        board[n_cols - 1][x] = 1;
+    for x in 0..n_cols {
+        board[(x, 0)] = 1;
+        board[(x, n_rows - 1)] = 1;
     }
-    for x in 1..(n_cols - 1) {
-        board[x][0] = 1;
-        board[x][n_rows - 1] = 1;
+    for y in 1..(n_rows.max(1) - 1) {
+        board[(0, y)] = 1;
+        board[(n_cols - 1, y)] = 1;
     }
 }
 
-#[allow(clippy::needless_range_loop)]
 fn fill_visible(board: &Board, visible: &mut Board) {
-    let (n_rows, n_cols) = get_dimensions(board);
+    let (n_rows, n_cols) = (board.height(), board.width());
 
     // top to bottom
-    let mut max_top_to_bottom = board[0].clone();
+    let mut max_top_to_bottom = board.row(0).to_vec();
     for x in 0..n_cols {
         for y in 0..n_rows {
-            if board[y][x] > max_top_to_bottom[x] {
-                max_top_to_bottom[x] = board[y][x];
-                visible[y][x] = 1;
+            if board[(x, y)] > max_top_to_bottom[x] {
+                max_top_to_bottom[x] = board[(x, y)];
+                visible[(x, y)] = 1;
             }
         }
     }
 
     // bottom to top
-    let mut max_bottom_to_top = board[n_rows - 1].clone();
+    let mut max_bottom_to_top = board.row(n_rows - 1).to_vec();
     for x in 0..n_cols {
         for y in (0..n_rows).rev() {
-            if board[y][x] > max_bottom_to_top[x] {
-                max_bottom_to_top[x] = board[y][x];
-                visible[y][x] = 1;
+            if board[(x, y)] > max_bottom_to_top[x] {
+                max_bottom_to_top[x] = board[(x, y)];
+                visible[(x, y)] = 1;
             }
         }
     }
 
     // left to right
-    let mut max_left_to_right: Vec<u32> = board.iter().map(|row| row[0]).collect();
+    let mut max_left_to_right: Vec<u32> = board.column(0).copied().collect();
     for y in 0..n_rows {
         for x in 0..n_cols {
-            if board[y][x] > max_left_to_right[y] {
-                max_left_to_right[y] = board[y][x];
-                visible[y][x] = 1;
+            if board[(x, y)] > max_left_to_right[y] {
+                max_left_to_right[y] = board[(x, y)];
+                visible[(x, y)] = 1;
             }
         }
     }
 
     // right to let
-    let mut max_right_to_left: Vec<u32> = board.iter().map(|row| row[n_cols - 1]).collect();
+    let mut max_right_to_left: Vec<u32> = board.column(n_cols - 1).copied().collect();
     for x in (0..n_cols).rev() {
         for y in 0..n_rows {
-            if board[y][x] > max_right_to_left[y] {
-                max_right_to_left[y] = board[y][x];
-                visible[y][x] = 1;
+            if board[(x, y)] > max_right_to_left[y] {
+                max_right_to_left[y] = board[(x, y)];
+                visible[(x, y)] = 1;
             }
         }
     }
 }
 
 fn count_visible(board: &Board) -> usize {
-    board.iter().flatten().filter(|e| **e == 1).count()
+    board.iter().filter(|(_, e)| **e == 1).count()
 }
 
 fn fill_scores(board: &Board, scores: &mut Board) {
-    let (n_rows, n_cols) = get_dimensions(board);
+    let (n_rows, n_cols) = (board.height(), board.width());
 
     for y in 0..n_rows {
         for x in 0..n_cols {
             let visible_up = (0..y)
                 .rev()
                 .fold_while(0, |acc, new_y| {
-                    if board[new_y][x] < board[y][x] {
+                    if board[(x, new_y)] < board[(x, y)] {
                         Continue(acc + 1)
                     } else {
                         Done(acc + 1)
@@ -144,7 +169,7 @@ fn fill_scores(board: &Board, scores: &mut Board) {
 
             let visible_down = ((y + 1)..n_rows)
                 .fold_while(0, |acc, new_y| {
-                    if board[new_y][x] < board[y][x] {
+                    if board[(x, new_y)] < board[(x, y)] {
                         Continue(acc + 1)
                     } else {
                         Done(acc + 1)
@@ -155,7 +180,7 @@ fn fill_scores(board: &Board, scores: &mut Board) {
             let visible_left = (0..x)
                 .rev()
                 .fold_while(0, |acc, new_x| {
-                    if board[y][new_x] < board[y][x] {
+                    if board[(new_x, y)] < board[(x, y)] {
                         Continue(acc + 1)
                     } else {
                         Done(acc + 1)
@@ -165,7 +190,7 @@ fn fill_scores(board: &Board, scores: &mut Board) {
 
             let visible_right = ((x + 1)..n_cols)
                 .fold_while(0, |acc, new_x| {
-                    if board[y][new_x] < board[y][x] {
+                    if board[(new_x, y)] < board[(x, y)] {
                         Continue(acc + 1)
                     } else {
                         Done(acc + 1)
@@ -173,19 +198,21 @@ fn fill_scores(board: &Board, scores: &mut Board) {
                 })
                 .into_inner();
 
-            scores[y][x] = visible_up * visible_down * visible_left * visible_right;
+            scores[(x, y)] = visible_up * visible_down * visible_left * visible_right;
         }
     }
 }
 
 fn highest_score(board: &Board) -> Option<u32> {
-    board.iter().flatten().max().copied()
+    board.iter().map(|(_, e)| *e).max()
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 8);
+    let input = &advent_of_code::read_input(8);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+    advent_of_code::compare!(1, PART_ONE_VARIANTS, input);
+    advent_of_code::compare!(2, PART_TWO_VARIANTS, input);
 }
 
 #[cfg(test)]
@@ -197,7 +224,7 @@ mod tests {
         let input = "123\n456\n789";
         assert_eq!(
             parse_input(input),
-            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9],]
+            Grid::from_vec(3, vec![1, 2, 3, 4, 5, 6, 7, 8, 9])
         );
     }
 
@@ -212,4 +239,25 @@ mod tests {
         let input = advent_of_code::read_file("examples", 8);
         assert_eq!(part_two(&input), Some(8));
     }
+
+    #[test]
+    fn test_not_square() {
+        // filling the borders used to swap rows and columns, and panic here.
+        let wide = "30373\n25512\n65332";
+        assert_eq!(part_one(wide), Some(14));
+        assert_eq!(part_two(wide), Some(2));
+
+        let tall = "12\n34\n56\n78\n90";
+        assert_eq!(part_one(tall), Some(10));
+        assert_eq!(part_two(tall), Some(0));
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_naive_property() {
+        advent_of_code::generators::check_property(8, 12, |input| {
+            advent_of_code::differential::assert_agree(PART_ONE_VARIANTS, input);
+            advent_of_code::differential::assert_agree(PART_TWO_VARIANTS, input);
+        });
+    }
 }
@@ -1,8 +1,8 @@
+use advent_of_code::helpers::{Dir4, Point2};
 use itertools::Itertools;
 use itertools::MinMaxResult::MinMax;
 use parse_display::{Display, FromStr};
 use rustc_hash::FxHashSet;
-use std::fmt;
 
 pub fn part_one(input: &str) -> Option<u32> {
     solve_with_size(input, 2)
@@ -23,22 +23,10 @@ fn solve_with_size(input: &str, size: usize) -> Option<u32> {
     Some(state.visited.len() as u32)
 }
 
-#[derive(Display, FromStr, PartialEq, Debug)]
-enum Direction {
-    #[display("R")]
-    Right,
-    #[display("D")]
-    Down,
-    #[display("L")]
-    Left,
-    #[display("U")]
-    Up,
-}
-
 #[derive(Display, FromStr, PartialEq, Debug)]
 #[display("{dir} {moves}")]
 struct Instruction {
-    dir: Direction,
+    dir: Dir4,
     moves: u8,
 }
 
@@ -47,42 +35,12 @@ fn parse_input(input: &str) -> Vec<Instruction> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 9);
+    let input = &advent_of_code::read_input(9);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[derive(PartialEq, Clone, Copy, Eq, Hash)]
-struct Coords {
-    x: i32,
-    y: i32,
-}
-
-impl fmt::Debug for Coords {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-impl std::ops::Add for Coords {
-    type Output = Coords;
-
-    fn add(self, other: Coords) -> Coords {
-        Coords {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-impl std::ops::AddAssign for Coords {
-    fn add_assign(&mut self, other: Coords) {
-        *self = Coords {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
+type Coords = Point2<i32>;
 
 #[derive(PartialEq, Debug)]
 struct State {
@@ -91,7 +49,7 @@ struct State {
 }
 
 fn start() -> Coords {
-    Coords { x: 0, y: 0 }
+    Coords::default()
 }
 
 fn initial_state(size: usize) -> State {
@@ -113,9 +71,9 @@ fn _print_state(state: &State) {
     if let (MinMax(min_x, max_x), MinMax(min_y, max_y)) =
         (all_xs.iter().minmax(), all_ys.iter().minmax())
     {
-        for y in (*min_y..=*max_y).rev() {
+        for y in *min_y..=*max_y {
             for x in *min_x..=*max_x {
-                let current: Coords = Coords { x, y };
+                let current = Coords::new(x, y);
                 if rope_as_set.contains(&current) && state.visited.contains(&current) {
                     print!("O");
                 } else if rope_as_set.contains(&current) {
@@ -132,39 +90,15 @@ fn _print_state(state: &State) {
 }
 
 fn should_move(head: Coords, tail: Coords) -> Option<Coords> {
-    let diff_x = head.x - tail.x;
-    let diff_y = head.y - tail.y;
-
-    match (diff_x, diff_y) {
-        (-2, 0) | (2, 0) | (0, -2) | (0, 2) => Some(Coords {
-            x: tail.x + diff_x / 2,
-            y: tail.y + diff_y / 2,
-        }),
-        (-1, -1) | (-1, 1) | (1, -1) | (1, 1) => None,
-        (-1, 0) | (1, 0) | (0, -1) | (0, 1) => None,
-        (0, 0) => None,
-        _ => Some(Coords {
-            x: tail.x + diff_x / diff_x.abs(),
-            y: tail.y + diff_y / diff_y.abs(),
-        }),
+    if head.chebyshev(tail) > 1 {
+        Some(tail + (head - tail).signum())
+    } else {
+        None
     }
 }
 
 fn splat_instruction(ins: &Instruction) -> (Coords, u8) {
-    let step = match &ins.dir {
-        Direction::Up => (0, 1),
-        Direction::Down => (0, -1),
-        Direction::Right => (1, 0),
-        Direction::Left => (-1, 0),
-    };
-
-    (
-        Coords {
-            x: step.0,
-            y: step.1,
-        },
-        ins.moves,
-    )
+    (ins.dir.step(), ins.moves)
 }
 
 fn run_instruction(ins: &Instruction, state: &mut State) {
@@ -191,8 +125,8 @@ fn run_instruction(ins: &Instruction, state: &mut State) {
 
 #[cfg(test)]
 mod tests {
-    use super::Direction::*;
     use super::*;
+    use advent_of_code::helpers::Dir4::*;
 
     #[test]
     fn test_parse() {
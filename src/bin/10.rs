@@ -1,9 +1,9 @@
-use itertools::Itertools;
+use advent_of_code::helpers::{ocr, Grid};
 use parse_display::{Display, FromStr};
 
 pub fn part_one(input: &str) -> Option<i32> {
     let result = run_input(input, 1);
-    let desired_indexes = vec![20, 60, 100, 140, 180, 220];
+    let desired_indexes = [20, 60, 100, 140, 180, 220];
 
     Some(
         desired_indexes
@@ -15,19 +15,35 @@ pub fn part_one(input: &str) -> Option<i32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<String> {
+    read_screen(&render_screen(input))
+}
+
+// the letters on the screen, or `None` after showing what could not be read.
+fn read_screen(screen: &Grid<bool>) -> Option<String> {
+    match ocr::recognize(screen) {
+        Ok(text) => Some(text),
+        Err(err) => {
+            eprintln!("{}", err);
+            print_screen(screen);
+            None
+        }
+    }
+}
+
+// the pixels lit during the first 240 cycles, 40 per row.
+fn render_screen(input: &str) -> Grid<bool> {
     let result = run_input(input, 1);
     let mut screen_state: Vec<bool> = vec![];
 
-    for (index, (_, x_value)) in result.iter().enumerate() {
+    for (index, (_, x_value)) in result.iter().take(240).enumerate() {
         let sprite_range = (x_value - 1)..=(x_value + 1);
         let normalized_index = index as i32 % 40;
         screen_state.push(sprite_range.contains(&normalized_index));
     }
+    screen_state.resize(240, false);
 
-    print_screen(&screen_state);
-
-    Some(screen_state.iter().filter(|e| **e).count() as u32)
+    Grid::from_vec(40, screen_state)
 }
 
 fn run_input(input: &str, initial: i32) -> Vec<(Instruction, i32)> {
@@ -49,19 +65,18 @@ fn run_input(input: &str, initial: i32) -> Vec<(Instruction, i32)> {
     result
 }
 
-fn print_screen(screen: &[bool]) {
-    let rows = screen.iter().map(|v| if *v { '#' } else { ' ' }).chunks(40);
-    for row in &rows {
-        let result = row.collect::<String>();
-
-        if result.len() == 40 {
-            println!("{}", result);
-        }
+fn print_screen(screen: &Grid<bool>) {
+    for row in screen.rows() {
+        let result = row
+            .iter()
+            .map(|v| if *v { '#' } else { ' ' })
+            .collect::<String>();
+        eprintln!("{}", result);
     }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 10);
+    let input = &advent_of_code::read_input(10);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -99,24 +114,32 @@ mod tests {
     }
 
     #[test]
-    fn test_part_two_short() {
-        let input = "addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1";
-        assert_eq!(part_two(input), Some(12));
+    fn test_render_screen() {
+        let input = advent_of_code::read_file("examples", 10);
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(render_screen(&input), Grid::parse(expected, |c| c == '#'));
     }
 
     #[test]
     fn test_part_two() {
+        // the example draws stripes rather than letters.
         let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(part_two(&input), Some(124));
+        assert_eq!(part_two(&input), None);
+    }
+
+    #[test]
+    fn test_read_screen() {
+        // every letter of the font, eight at a time like on the 40 pixels wide screen.
+        for word in ["ABCEFGHI", "JKLOPRSU", "YZHELLOS"] {
+            let screen = ocr::SMALL.render(word).unwrap();
+            assert_eq!(screen.width(), 40);
+            assert_eq!(read_screen(&screen), Some(word.to_string()));
+        }
     }
 }
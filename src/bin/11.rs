@@ -1,34 +1,50 @@
-pub fn part_one(input: &str) -> Option<u64> {
-    if let Ok(("", mut monkeys)) = monkeys_parser(input) {
-        for _ in 0..20 {
-            run_round(&mut monkeys, |x| x / 3);
-        }
+use advent_of_code::helpers::checked::{Checked, Overflow};
+use advent_of_code::helpers::num::{lcm_all, Integer};
+use advent_of_code::helpers::parse::{comma_list, parse_all, uint};
 
-        find_score(&monkeys)
+pub fn part_one(input: &str) -> Option<u64> {
+    if let Ok(monkeys) = parse_all(monkeys_parser, input) {
+        keep_away::<u64>(&monkeys, 20, 3)
+            .or_else(|_| keep_away::<u128>(&monkeys, 20, 3))
+            .inspect_err(|overflow| eprintln!("{}", overflow))
+            .ok()
     } else {
         None
     }
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    if let Ok(("", mut monkeys)) = monkeys_parser(input) {
-        for _ in 0..10000 {
-            run_round(&mut monkeys, |x| x);
-        }
-
-        find_score(&monkeys)
+    if let Ok(monkeys) = parse_all(monkeys_parser, input) {
+        keep_away::<u64>(&monkeys, 10000, 1)
+            .or_else(|_| keep_away::<u128>(&monkeys, 10000, 1))
+            .inspect_err(|overflow| eprintln!("{}", overflow))
+            .ok()
     } else {
         None
     }
 }
 
+// worry levels are stored as `W`, in case their squares don't fit in `u64`.
+fn keep_away<W: Integer + From<u64>>(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: u64,
+) -> Result<u64, Overflow<W>> {
+    let mut monkeys: Vec<Monkey<W>> = monkeys.iter().map(Monkey::widen).collect();
+    for _ in 0..rounds {
+        run_round(&mut monkeys, relief)?;
+    }
+
+    Ok(find_score(&monkeys))
+}
+
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 11);
+    let input = &advent_of_code::read_input(11);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
-fn _print_monkeys(monkeys: &Vec<Monkey>) {
+fn _print_monkeys<W: Integer>(monkeys: &Vec<Monkey<W>>) {
     for monkey in monkeys {
         println!(
             "Monkey {} ({}): {:?}",
@@ -38,7 +54,7 @@ fn _print_monkeys(monkeys: &Vec<Monkey>) {
     println!()
 }
 
-fn find_score(monkeys: &[Monkey]) -> Option<u64> {
+fn find_score<W>(monkeys: &[Monkey<W>]) -> u64 {
     let mut counts = monkeys
         .iter()
         .map(|m| m.inspected_items)
@@ -47,7 +63,7 @@ fn find_score(monkeys: &[Monkey]) -> Option<u64> {
     counts.sort();
     counts.reverse();
 
-    Some(counts[0] * counts[1])
+    counts[0] * counts[1]
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -65,57 +81,72 @@ enum OpFun {
 type Op = (OpArg, OpFun, OpArg);
 
 #[derive(Debug, PartialEq, Clone)]
-struct Monkey {
+struct Monkey<W = u64> {
     id: u64,
-    items: Vec<u64>,
+    items: Vec<W>,
     inspected_items: u64,
     op: Op,
     next: (u64, u64, u64),
 }
 
-fn run_round<F: FnMut(u64) -> u64>(monkeys: &mut Vec<Monkey>, mut after_run_op: F) {
-    let modulus : u64 = monkeys.iter().map(|m| m.next.0).product();
+impl Monkey {
+    fn widen<W: From<u64>>(&self) -> Monkey<W> {
+        Monkey {
+            id: self.id,
+            items: self.items.iter().map(|item| W::from(*item)).collect(),
+            inspected_items: self.inspected_items,
+            op: self.op,
+            next: self.next,
+        }
+    }
+}
+
+fn run_round<W: Integer + From<u64>>(
+    monkeys: &mut [Monkey<W>],
+    relief: u64,
+) -> Result<(), Overflow<W>> {
+    // every test still gives the same answer modulo a multiple of all the divisors.
+    let modulus = W::from(lcm_all(monkeys.iter().map(|m| m.next.0)));
 
     for index in 0..monkeys.len() {
         let current = monkeys[index].clone();
 
         for item in current.items {
             let (rem_by, fst, snd) = current.next;
-            let new_item = after_run_op(run_op(current.op, item) % modulus);
-            let dest_monkey = if new_item % rem_by == 0 { fst } else { snd };
+            let new_item =
+                (run_op(current.op, Checked::new(item)) % modulus / W::from(relief)).get()?;
+            let dest_monkey = if new_item % W::from(rem_by) == W::ZERO {
+                fst
+            } else {
+                snd
+            };
             monkeys[dest_monkey as usize].items.push(new_item);
         }
 
         monkeys[index].inspected_items += monkeys[index].items.len() as u64;
         monkeys[index].items = vec![];
     }
+
+    Ok(())
 }
 
-fn run_op(op: Op, old: u64) -> u64 {
-    let fst = if OpArg::Old == op.0 {
-        OpArg::Int(old)
-    } else {
-        op.0
-    };
-    let snd = if OpArg::Old == op.2 {
-        OpArg::Int(old)
-    } else {
-        op.2
+fn run_op<W: Integer + From<u64>>(op: Op, old: Checked<W>) -> Checked<W> {
+    let value = |arg| match arg {
+        OpArg::Old => old,
+        OpArg::Int(x) => Checked::new(W::from(x)),
     };
 
-    match (fst, op.1, snd) {
-        (OpArg::Int(x), OpFun::Add, OpArg::Int(y)) => x + y,
-        (OpArg::Int(x), OpFun::Mul, OpArg::Int(y)) => x * y,
-        _ => panic!("run_op could not replace all args"),
+    match op.1 {
+        OpFun::Add => value(op.0) + value(op.2),
+        OpFun::Mul => value(op.0) * value(op.2),
     }
 }
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::digit1,
     combinator::{map, opt},
-    multi::{many1, separated_list1},
+    multi::many1,
     sequence::{preceded, terminated, tuple},
     IResult,
 };
@@ -124,20 +155,12 @@ fn monkeys_parser(input: &str) -> IResult<&str, Vec<Monkey>> {
     many1(terminated(monkey_parser, opt(tag("\n"))))(input)
 }
 
-fn int_parser(input: &str) -> IResult<&str, u64> {
-    map(digit1, |s: &str| s.parse().unwrap())(input)
-}
-
-fn list_int_parser(input: &str) -> IResult<&str, Vec<u64>> {
-    separated_list1(tag(", "), int_parser)(input)
-}
-
 fn op_parser(input: &str) -> IResult<&str, Op> {
     tuple((op_arg_parser, op_fun_parser, op_arg_parser))(input)
 }
 
 fn op_arg_parser(input: &str) -> IResult<&str, OpArg> {
-    alt((map(tag("old"), |_| OpArg::Old), map(int_parser, OpArg::Int)))(input)
+    alt((map(tag("old"), |_| OpArg::Old), map(uint, OpArg::Int)))(input)
 }
 
 fn op_fun_parser(input: &str) -> IResult<&str, OpFun> {
@@ -149,18 +172,18 @@ fn op_fun_parser(input: &str) -> IResult<&str, OpFun> {
 
 fn next_parser(input: &str) -> IResult<&str, (u64, u64, u64)> {
     tuple((
-        preceded(tag("  Test: divisible by "), int_parser),
-        preceded(tag("\n    If true: throw to monkey "), int_parser),
-        preceded(tag("\n    If false: throw to monkey "), int_parser),
+        preceded(tag("  Test: divisible by "), uint),
+        preceded(tag("\n    If true: throw to monkey "), uint),
+        preceded(tag("\n    If false: throw to monkey "), uint),
     ))(input)
 }
 
 fn monkey_parser(input: &str) -> IResult<&str, Monkey> {
     map(
         tuple((
-            terminated(preceded(tag("Monkey "), int_parser), tag(":\n")),
+            terminated(preceded(tag("Monkey "), uint), tag(":\n")),
             terminated(
-                preceded(tag("  Starting items: "), list_int_parser),
+                preceded(tag("  Starting items: "), comma_list(uint)),
                 tag("\n"),
             ),
             terminated(preceded(tag("  Operation: new = "), op_parser), tag("\n")),
@@ -191,4 +214,30 @@ mod tests {
         let input = advent_of_code::read_file("examples", 11);
         assert_eq!(part_two(&input), Some(2713310158));
     }
+
+    #[test]
+    fn test_wide_worry_levels() {
+        // the first square is 2^64, one more than fits in u64.
+        let input = "Monkey 0:
+  Starting items: 4294967296
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 3
+  Operation: new = old + 1
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+        let monkeys = parse_all(monkeys_parser, input).unwrap();
+        let overflow = keep_away::<u64>(&monkeys, 10000, 1).unwrap_err();
+        assert_eq!(overflow.lhs, 4294967296);
+        assert_eq!(part_two(input), keep_away::<u128>(&monkeys, 10000, 1).ok());
+        assert!(keep_away::<u64>(&monkeys, 20, 3).is_err());
+        assert_eq!(part_one(input), keep_away::<u128>(&monkeys, 20, 3).ok());
+        assert!(part_one(input).is_some());
+    }
 }
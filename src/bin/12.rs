@@ -1,46 +1,41 @@
+use advent_of_code::helpers::grid::{Grid, Pos};
+use advent_of_code::helpers::search::bfs_cost;
+use std::fmt;
+
 pub fn part_one(input: &str) -> Option<usize> {
     let board = Board::from(input);
 
-    run_dijkstra(
-        &[board.start],
+    bfs_cost(
+        [board.start],
         |node| board.successors(node),
         |node| node == &board.end,
     )
-    .map(|(_, count)| count)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     let board = Board::from(input);
 
-    let mut starting_cells: Vec<Coords> = vec![];
-
-    for (y, row) in board.cells.iter().enumerate() {
-        for (x, cell) in row.iter().enumerate() {
-            if let Cell::Value('a') = cell {
-                starting_cells.push(Coords { x, y })
-            }
-        }
-    }
+    let starting_cells: Vec<Coords> = board
+        .cells
+        .iter()
+        .filter(|(_, cell)| **cell == Cell::Value('a'))
+        .map(|(pos, _)| pos)
+        .collect();
 
-    run_dijkstra(
-        &starting_cells,
+    bfs_cost(
+        starting_cells,
         |node| board.successors(node),
         |node| node == &board.end,
     )
-    .map(|(_, count)| count)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 12);
+    let input = &advent_of_code::read_input(12);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Coords {
-    x: usize,
-    y: usize,
-}
+type Coords = Pos;
 
 #[derive(Debug, PartialEq)]
 enum Cell {
@@ -59,185 +54,55 @@ impl Cell {
     }
 }
 
-impl ToString for Cell {
-    fn to_string(&self) -> String {
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Cell::Start => "S".to_string(),
-            Cell::End => "E".to_string(),
-            Cell::Value(c) => format!("{}", c),
+            Cell::Start => write!(f, "S"),
+            Cell::End => write!(f, "E"),
+            Cell::Value(c) => write!(f, "{}", c),
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
 struct Board {
-    num_rows: i32,
-    num_cols: i32,
     start: Coords,
     end: Coords,
-    cells: Vec<Vec<Cell>>,
+    cells: Grid<Cell>,
 }
 
 impl Board {
-    fn successors(&self, coords: &Coords) -> Vec<(Coords, usize)> {
-        let &Coords { x, y } = coords;
-        let offsets: Vec<(i32, i32)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-
-        offsets
-            .iter()
-            .filter_map(|offset| {
-                let (new_x, new_y) = (x as i32 + offset.0, y as i32 + offset.1);
-                if (0..self.num_rows).contains(&new_y) && (0..self.num_cols).contains(&new_x) {
-                    let new_score = self.cells[new_y as usize][new_x as usize].score();
-                    let old_score = self.cells[y][x].score();
+    fn successors(&self, coords: &Coords) -> impl Iterator<Item = Coords> + '_ {
+        let old_score = self.cells[*coords].score();
 
-                    if new_score - old_score <= 1 {
-                        Some((
-                            Coords {
-                                x: new_x as usize,
-                                y: new_y as usize,
-                            },
-                            1,
-                        ))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect()
+        self.cells
+            .neighbours4(*coords)
+            .filter(move |next| self.cells[*next].score() - old_score <= 1)
     }
 }
 
-impl ToString for Board {
-    fn to_string(&self) -> String {
-        self.cells
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<_>>()
-                    .join("")
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells)
     }
 }
 
 impl From<&str> for Board {
     fn from(input: &str) -> Self {
-        let mut start: Coords = Coords { x: 0, y: 0 };
-        let mut end: Coords = Coords { x: 0, y: 0 };
-
-        let cells: Vec<Vec<Cell>> = input
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, elem)| match elem {
-                        'S' => {
-                            start = Coords { x, y };
-                            Cell::Start
-                        }
-                        'E' => {
-                            end = Coords { x, y };
-                            Cell::End
-                        }
-                        other => Cell::Value(other),
-                    })
-                    .collect()
-            })
-            .collect();
+        let cells = Grid::parse(input, |elem| match elem {
+            'S' => Cell::Start,
+            'E' => Cell::End,
+            other => Cell::Value(other),
+        });
 
         Board {
-            num_rows: cells.len() as i32,
-            num_cols: cells[0].len() as i32,
-            start,
-            end,
+            start: cells.find(|c| *c == Cell::Start).unwrap(),
+            end: cells.find(|c| *c == Cell::End).unwrap(),
             cells,
         }
     }
 }
 
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-
-#[derive(Clone, Eq, PartialEq, Copy, Debug)]
-struct HeapState<T> {
-    node: T,
-    cost: usize,
-}
-
-// Manually implement Ord so we get a min-heap instead of a max-heap
-impl<T: Eq> Ord for HeapState<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl<T: Eq> PartialOrd for HeapState<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-use core::hash::Hash;
-use std::marker::Copy;
-
-fn run_dijkstra<T: Eq + Hash + Copy, S: FnMut(&T) -> Vec<(T, usize)>, E: FnMut(&T) -> bool>(
-    starting: &[T],
-    mut successors: S,
-    mut is_end: E,
-) -> Option<(Vec<T>, usize)> {
-    let mut best: HashMap<T, usize> = HashMap::new();
-    let mut parent: HashMap<T, T> = HashMap::new();
-    let mut heap = BinaryHeap::new();
-
-    for start in starting {
-        best.insert(*start, 0);
-
-        heap.push(HeapState {
-            node: *start,
-            cost: 0,
-        });
-    }
-
-    while let Some(HeapState { node, cost }) = heap.pop() {
-        if is_end(&node) {
-            let mut path = vec![];
-            let mut current = Some(&node);
-
-            path.push(node);
-            while let Some(prev) = current {
-                path.push(*prev);
-                current = parent.get(prev)
-            }
-            path.reverse();
-
-            return Some((path, cost));
-        }
-
-        for (next, next_cost) in successors(&node) {
-            let new_cost = cost + next_cost;
-
-            let next_state = HeapState {
-                node: next,
-                cost: new_cost,
-            };
-
-            if new_cost < *best.get(&next).unwrap_or(&std::usize::MAX) {
-                best.insert(next, new_cost);
-                parent.insert(next, node);
-                heap.push(next_state);
-            }
-        }
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,13 +113,35 @@ mod tests {
         let input = advent_of_code::read_file("examples", 12);
         let board = Board::from(&input[..]);
 
-        assert_eq!(board.start, Coords { x: 0, y: 0 });
-        assert_eq!(board.end, Coords { x: 5, y: 2 });
-        assert_eq!(board.num_rows, 5);
-        assert_eq!(board.num_cols, 8);
+        assert_eq!(board.start, (0, 0));
+        assert_eq!(board.end, (5, 2));
+        assert_eq!(board.cells.height(), 5);
+        assert_eq!(board.cells.width(), 8);
         assert_eq!(board.to_string(), input.trim());
     }
 
+    #[test]
+    fn test_path() {
+        use advent_of_code::helpers::search::bfs;
+        let input = advent_of_code::read_file("examples", 12);
+        let board = Board::from(&input[..]);
+
+        let (path, count) = bfs(
+            [board.start],
+            |node| board.successors(node),
+            |node| node == &board.end,
+        )
+        .unwrap();
+        assert_eq!(path.len(), count + 1);
+        assert_eq!(path.first(), Some(&board.start));
+        assert_eq!(path.last(), Some(&board.end));
+        // the end is only reached once, at the end of the path.
+        assert_eq!(path.iter().filter(|pos| **pos == board.end).count(), 1);
+        assert!(path
+            .windows(2)
+            .all(|w| board.successors(&w[0]).any(|n| n == w[1])));
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 12);
@@ -1,7 +1,8 @@
+use advent_of_code::helpers::parse::{blocks, parse_all, uint};
 use std::cmp::Ordering;
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let pairs: Vec<(Packet, Packet)> = pairs_parser(input).unwrap().1;
+    let pairs: Vec<(Packet, Packet)> = parse_all(pairs_parser, input).unwrap();
 
     Some(
         pairs
@@ -16,7 +17,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut all_packets: Vec<Packet> = all_parser(input).unwrap().1;
+    let mut all_packets: Vec<Packet> = parse_all(all_parser, input).unwrap();
 
     let two = List(vec![List(vec![Item(2)])]);
     let six = List(vec![List(vec![Item(6)])]);
@@ -40,7 +41,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 13);
+    let input = &advent_of_code::read_input(13);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -48,10 +49,10 @@ fn main() {
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{map, opt},
+    character::complete::line_ending,
+    combinator::map,
     multi::{many1, separated_list0, separated_list1},
-    sequence::{delimited, separated_pair, terminated},
+    sequence::{delimited, separated_pair},
     IResult,
 };
 
@@ -65,36 +66,27 @@ use crate::Packet::*;
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match (self, other) {
-            (List(_), Item(_)) => self.partial_cmp(&List(vec![other.clone()])),
-            (Item(_), List(_)) => List(vec![self.clone()]).partial_cmp(other),
-            (Item(x), Item(y)) => x.partial_cmp(y),
-            (List(x), List(y)) => x.partial_cmp(y),
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Packet {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        match (self, other) {
+            (List(_), Item(_)) => self.cmp(&List(vec![other.clone()])),
+            (Item(_), List(_)) => List(vec![self.clone()]).cmp(other),
+            (Item(x), Item(y)) => x.cmp(y),
+            (List(x), List(y)) => x.cmp(y),
+        }
     }
 }
 
 fn pairs_parser(i: &str) -> IResult<&str, Vec<(Packet, Packet)>> {
-    terminated(
-        separated_list1(
-            tag("\n\n"),
-            separated_pair(packet_parser, tag("\n"), packet_parser),
-        ),
-        opt(tag("\n")),
-    )(i)
+    blocks(separated_pair(packet_parser, line_ending, packet_parser))(i)
 }
 
 fn all_parser(i: &str) -> IResult<&str, Vec<Packet>> {
-    terminated(
-        many1(terminated(packet_parser, alt((tag("\n\n"), tag("\n"))))),
-        opt(tag("\n")),
-    )(i)
+    separated_list1(many1(line_ending), packet_parser)(i)
 }
 
 fn packet_parser(i: &str) -> IResult<&str, Packet> {
@@ -109,7 +101,7 @@ fn packet_parser(i: &str) -> IResult<&str, Packet> {
 }
 
 fn item_parser(i: &str) -> IResult<&str, Packet> {
-    map(digit1, |s: &str| Packet::Item(s.parse().unwrap()))(i)
+    map(uint, Packet::Item)(i)
 }
 
 #[cfg(test)]
@@ -159,4 +151,27 @@ mod tests {
         let input = advent_of_code::read_file("examples", 13);
         assert_eq!(part_two(&input), Some(140));
     }
+
+    // `[1]` and `[[1]]` compare as equal without being equal, so check for a total preorder.
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_total_order_property() {
+        advent_of_code::generators::check_property(13, 6, |input| {
+            let packets = all_parser(input).unwrap().1;
+
+            for a in &packets {
+                assert_eq!(a.cmp(a), Ordering::Equal);
+
+                for b in &packets {
+                    assert_eq!(a.cmp(b), b.cmp(a).reverse());
+
+                    for c in &packets {
+                        if a <= b && b <= c {
+                            assert!(a <= c, "{:?} {:?} {:?}", a, b, c);
+                        }
+                    }
+                }
+            }
+        });
+    }
 }
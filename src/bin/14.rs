@@ -1,10 +1,8 @@
-use itertools::Itertools;
-use itertools::MinMaxResult::MinMax;
-use rustc_hash::FxHashMap;
+use advent_of_code::helpers::{ints, SparseGrid};
 use std::cmp::{max, min};
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (_, sequences) = input_parser(input).unwrap();
+    let sequences = parse_input(input);
 
     let mut board: Board = build_board(sequences);
     fill_with_sand(&mut board);
@@ -13,12 +11,12 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (_, sequences) = input_parser(input).unwrap();
+    let sequences = parse_input(input);
     let mut board: Board = build_board(sequences);
 
-    let ((min_x, max_x), (_, max_y)) = get_bounds(&board);
-    for x in (min_x - 200)..(max_x + 200) {
-        board.grid.insert((x, max_y + 2), Cell::Rock);
+    let bounds = board.grid.bounds().unwrap();
+    for x in (bounds.min_x - 200)..(bounds.max_x + 200) {
+        board.grid.insert((x, bounds.max_y + 2), Cell::Rock);
     }
     fill_with_sand(&mut board);
 
@@ -26,14 +24,14 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 14);
+    let input = &advent_of_code::read_input(14);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
 // main logic
 fn fill_with_sand(board: &mut Board) {
-    let (_, (_, max_y)) = get_bounds(board);
+    let max_y = board.grid.bounds().unwrap().max_y;
 
     let mut to_visit = Vec::new();
     let drop_point: Coords = (500, 0);
@@ -41,7 +39,7 @@ fn fill_with_sand(board: &mut Board) {
 
     while let Some(v) = to_visit.last() {
         if v.1 < max_y {
-            match neighbours(v).iter().find(|n| board.grid.get(n).is_none()) {
+            match neighbours(v).iter().find(|n| !board.grid.contains(n)) {
                 Some(empty_tile) => to_visit.push(*empty_tile),
                 None => {
                     if let Some(value) = to_visit.pop() {
@@ -74,11 +72,11 @@ enum Cell {
 
 #[derive(Debug, PartialEq)]
 struct Board {
-    grid: FxHashMap<Coords, Cell>,
+    grid: SparseGrid<Cell>,
 }
 
 fn build_board(sequences: Vec<Vec<Coords>>) -> Board {
-    let mut result = FxHashMap::default();
+    let mut result = SparseGrid::new();
 
     for seq in sequences {
         let pairs: Vec<(_, _)> = seq.iter().zip(seq.iter().skip(1)).collect();
@@ -92,32 +90,13 @@ fn build_board(sequences: Vec<Vec<Coords>>) -> Board {
     Board { grid: result }
 }
 
-fn get_bounds(board: &Board) -> ((i32, i32), (i32, i32)) {
-    let (all_xs, all_ys): (Vec<i32>, Vec<i32>) = board.grid.keys().copied().unzip();
-
-    if let (MinMax(&min_x, &max_x), MinMax(&min_y, &max_y)) =
-        (all_xs.iter().minmax(), all_ys.iter().minmax())
-    {
-        ((min_x, max_x), (min_y, max_y))
-    } else {
-        panic!("could not find bounds")
-    }
-}
-
 fn _print_board(board: &Board) {
-    let ((min_x, max_x), (min_y, max_y)) = get_bounds(board);
-
-    for y in (min_y - 1)..=(max_y + 1) {
-        for x in (min_x - 1)..=(max_x + 1) {
-            match board.grid.get(&(x, y)) {
-                Some(Cell::Rock) => print!("#"),
-                Some(Cell::Sand) => print!("o"),
-                None => print!("."),
-            }
-        }
-        println!()
-    }
-    println!()
+    let result = board.grid.render(1, |_, cell| match cell {
+        Some(Cell::Rock) => '#',
+        Some(Cell::Sand) => 'o',
+        None => '.',
+    });
+    println!("{}\n", result);
 }
 
 fn coords_between(fst: &Coords, snd: &Coords) -> Vec<Coords> {
@@ -134,28 +113,15 @@ fn coords_between(fst: &Coords, snd: &Coords) -> Vec<Coords> {
     }
 }
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{map, opt},
-    multi::separated_list1,
-    sequence::{separated_pair, terminated},
-    IResult,
-};
-
-fn input_parser(i: &str) -> IResult<&str, Vec<Vec<Coords>>> {
-    terminated(
-        separated_list1(tag("\n"), separated_list1(tag(" -> "), coords_parser)),
-        opt(tag("\n")),
-    )(i)
-}
-
-fn coords_parser(i: &str) -> IResult<&str, Coords> {
-    separated_pair(int_parser, tag(","), int_parser)(i)
-}
-
-fn int_parser(i: &str) -> IResult<&str, i32> {
-    map(digit1, |s: &str| s.parse().unwrap())(i)
+fn parse_input(input: &str) -> Vec<Vec<Coords>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split(" -> ")
+                .map(|coords| ints(coords).unwrap())
+                .collect()
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -166,14 +132,15 @@ mod tests {
     fn test_parse() {
         let input = advent_of_code::read_file("examples", 14);
         assert_eq!(
-            input_parser(&input),
-            Ok((
-                "",
-                vec![
-                    vec![(498, 4), (498, 6), (496, 6)],
-                    vec![(503, 4), (502, 4), (502, 9), (494, 9)]
-                ]
-            ))
+            parse_input(&input),
+            vec![
+                vec![(498, 4), (498, 6), (496, 6)],
+                vec![(503, 4), (502, 4), (502, 9), (494, 9)]
+            ]
+        );
+        assert_eq!(
+            parse_input("-2,4 -> -2,-6\n"),
+            vec![vec![(-2, 4), (-2, -6)]]
         );
     }
 
@@ -1,9 +1,8 @@
-use itertools::Itertools;
-use parse_display::{Display, FromStr};
+use advent_of_code::differential::Variant;
+use advent_of_code::helpers::sparse_grid::{Bounds, SparseGrid};
+use advent_of_code::helpers::{ints, Interval, IntervalSet, Point2};
 use rayon::iter::IntoParallelIterator;
 use rayon::prelude::*;
-use std::cmp::{max, min};
-use std::collections::HashMap;
 
 pub fn part_one(input: &str) -> Option<i32> {
     part_one_internal(2_000_000, input)
@@ -13,6 +12,13 @@ pub fn part_two(input: &str) -> Option<i64> {
     part_two_internal(4_000_000, input)
 }
 
+const PART_TWO_VARIANTS: &[Variant<i64>] = &[
+    ("sensor_borders", part_two),
+    ("row_ranges", |input| {
+        part_two_internal_rows(4_000_000, input)
+    }),
+];
+
 fn part_one_internal(row: i32, input: &str) -> Option<i32> {
     let lines = parse_input(input);
     let board: Board = fill_board(lines);
@@ -23,32 +29,24 @@ fn part_one_internal(row: i32, input: &str) -> Option<i32> {
         .copied()
         .collect();
 
-    Some(
-        build_ranges(row, &board.sensors)
-            .iter()
-            .map(|(start, end)| {
-                let matching_beacons = beacons
-                    .iter()
-                    .filter(|pos| pos.y == row && (start..=end).contains(&&pos.x))
-                    .count();
-                end - start + 1 - (matching_beacons as i32)
-            })
-            .sum(),
-    )
+    let coverage = row_coverage(row, &board.sensors);
+    let matching_beacons = beacons
+        .iter()
+        .filter(|pos| pos.y == row && coverage.contains(pos.x))
+        .count();
+
+    Some(coverage.covered_len() - matching_beacons as i32)
 }
 
-fn _part_two_internal_alt(limit: i32, input: &str) -> Option<i64> {
+fn part_two_internal_rows(limit: i32, input: &str) -> Option<i64> {
     let lines = parse_input(input);
     let board: Board = fill_board(lines);
 
     (0..=limit).into_par_iter().find_map_any(|y| {
-        let ranges: Vec<(i32, i32)> = build_ranges(y, &board.sensors);
-        // when we find two ranges, we found the hole
-        if let [(_, before_result), (_, _)] = ranges[..] {
-            Some((before_result + 1) as i64 * 4_000_000 + y as i64)
-        } else {
-            None
-        }
+        // the hole is the only cell of the search area that no sensor covers
+        let uncovered = row_coverage(y, &board.sensors).complement(Interval::new(0, limit));
+        let hole = uncovered.as_slice().first()?;
+        Some(hole.start as i64 * 4_000_000 + y as i64)
     })
 }
 
@@ -71,7 +69,7 @@ fn part_two_internal(limit: i32, input: &str) -> Option<i64> {
                         // if there is at least one point which is not reachable
                         // from any other sensor, then it must be the solution
                         for (other, other_radius) in board.sensors.iter() {
-                            if other.distance(Pos { x, y }) <= *other_radius {
+                            if other.manhattan(Pos::new(x, y)) <= *other_radius {
                                 break 'outer;
                             }
                         }
@@ -83,49 +81,22 @@ fn part_two_internal(limit: i32, input: &str) -> Option<i64> {
     })
 }
 
-fn build_ranges(row: i32, sensors: &[(Pos, i32)]) -> Vec<(i32, i32)> {
-    let sorted_ranges: Vec<(i32, i32)> = sensors
+// the cells of `row` within reach of a sensor.
+fn row_coverage(row: i32, sensors: &[(Pos, i32)]) -> IntervalSet<i32> {
+    sensors
         .iter()
         .filter_map(|(sensor, radius)| {
-            let distance = sensor.distance(Pos {
-                x: sensor.x,
-                y: row,
-            });
-            if distance <= *radius {
-                let x_offset = radius - (sensor.y - row).abs();
-                Some((sensor.x - x_offset, sensor.x + x_offset))
-            } else {
-                None
-            }
+            let x_offset = radius - (sensor.y - row).abs();
+            Interval::try_new(sensor.x - x_offset, sensor.x + x_offset)
         })
-        .sorted_by_key(|e| e.0)
-        .collect();
-
-    let mut result: Vec<(i32, i32)> = vec![sorted_ranges[0]];
-    for curr in sorted_ranges[1..].iter() {
-        if let Some(&last) = &result.last() {
-            if touch_or_overlap(&last, curr) {
-                let new_range: (i32, i32) = (min(last.0, curr.0), max(last.1, curr.1));
-                if new_range != last {
-                    result.pop();
-                    result.push(new_range)
-                }
-            } else {
-                result.push(*curr)
-            }
-        }
-    }
-    result
-}
-
-fn touch_or_overlap((x1, x2): &(i32, i32), (y1, y2): &(i32, i32)) -> bool {
-    !(x1 > y2 || y1 > &(x2 + 1))
+        .collect()
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 15);
+    let input = &advent_of_code::read_input(15);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+    advent_of_code::compare!(2, PART_TWO_VARIANTS, input);
 }
 
 #[derive(PartialEq, Debug)]
@@ -136,23 +107,23 @@ enum Cell {
 
 #[derive(PartialEq, Debug)]
 struct Board {
-    grid: HashMap<Pos, Cell>,
+    grid: SparseGrid<Cell, Pos>,
     sensors: Vec<(Pos, i32)>,
     bounds: ((i32, i32), (i32, i32)),
 }
 
 fn fill_board(info: Vec<SensorInfo>) -> Board {
-    let mut grid = HashMap::new();
+    let mut grid = SparseGrid::new();
     let mut sensors = Vec::new();
-    let mut min_x = std::i32::MAX;
-    let mut min_y = std::i32::MAX;
-    let mut max_x = std::i32::MIN;
-    let mut max_y = std::i32::MIN;
+    let mut min_x = i32::MAX;
+    let mut min_y = i32::MAX;
+    let mut max_x = i32::MIN;
+    let mut max_y = i32::MIN;
 
     for elem in info {
         grid.insert(elem.sensor, Cell::Sensor);
         grid.insert(elem.nearest_beacon, Cell::Beacon);
-        let distance = elem.sensor.distance(elem.nearest_beacon);
+        let distance = elem.sensor.manhattan(elem.nearest_beacon);
         sensors.push((elem.sensor, distance));
 
         if elem.sensor.x - distance < min_x {
@@ -181,44 +152,30 @@ fn fill_board(info: Vec<SensorInfo>) -> Board {
 
 fn _print_board(board: &Board) {
     let ((min_x, max_x), (min_y, max_y)) = board.bounds;
-
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            let row_ranges = build_ranges(y, &board.sensors);
-            match board.grid.get(&Pos { x, y }) {
-                Some(Cell::Sensor) => print!("S"),
-                Some(Cell::Beacon) => print!("B"),
-                None => {
-                    if row_ranges
-                        .iter()
-                        .any(|(start, end)| *start <= x && x <= *end)
-                    {
-                        print!("#")
-                    } else {
-                        print!(".")
-                    }
-                }
+    let area = Bounds {
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+    };
+
+    let result = board.grid.render_in(area, |Pos { x, y }, cell| match cell {
+        Some(Cell::Sensor) => 'S',
+        Some(Cell::Beacon) => 'B',
+        None => {
+            if row_coverage(y, &board.sensors).contains(x) {
+                '#'
+            } else {
+                '.'
             }
         }
-        println!()
-    }
+    });
+    println!("{}", result);
 }
 
-#[derive(Display, FromStr, PartialEq, Debug, Eq, Hash, Clone, Copy)]
-#[display("x={x}, y={y}")]
-struct Pos {
-    x: i32,
-    y: i32,
-}
-
-impl Pos {
-    fn distance(self, other: Pos) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-}
+type Pos = Point2<i32>;
 
-#[derive(Display, FromStr, PartialEq, Debug)]
-#[display("Sensor at {sensor}: closest beacon is at {nearest_beacon}")]
+#[derive(PartialEq, Debug)]
 struct SensorInfo {
     sensor: Pos,
     nearest_beacon: Pos,
@@ -227,7 +184,13 @@ struct SensorInfo {
 fn parse_input(input: &str) -> Vec<SensorInfo> {
     input
         .lines()
-        .map(|line| line.parse::<SensorInfo>().unwrap())
+        .map(|line| {
+            let [sx, sy, bx, by] = ints(line).unwrap();
+            SensorInfo {
+                sensor: Pos::new(sx, sy),
+                nearest_beacon: Pos::new(bx, by),
+            }
+        })
         .collect()
 }
 
@@ -258,4 +221,16 @@ mod tests {
         let input = advent_of_code::read_file("examples", 15);
         assert_eq!(part_two_internal(20, &input), Some(56_000_011));
     }
+
+    #[test]
+    fn test_part_two_variants() {
+        let input = advent_of_code::read_file("examples", 15);
+        advent_of_code::differential::assert_agree(
+            &[
+                ("sensor_borders", |input| part_two_internal(20, input)),
+                ("row_ranges", |input| part_two_internal_rows(20, input)),
+            ],
+            &input,
+        );
+    }
 }
@@ -1,240 +1,145 @@
-use rustc_hash::{FxHashMap, FxHashSet};
+use advent_of_code::helpers::graph::Graph;
+use advent_of_code::helpers::memo::Table;
+use advent_of_code::helpers::parse::{lines, parse_all, uint};
+use advent_of_code::helpers::BitSet;
+use nom::{
+    branch::alt, bytes::complete::tag, character::complete::alphanumeric1, combinator::map,
+    multi::separated_list1, sequence::tuple, IResult,
+};
+use rustc_hash::FxHashMap;
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let (_, valves) = valves_parser(input).unwrap();
-    let valves_map: FxHashMap<&str, &Valve> = valves.iter().map(|v| (v.name, v)).collect();
-    Some(find_best_throughput("AA", 30, &valves_map))
+    let valves = parse_all(valves_parser, input).unwrap();
+    let cave = Cave::new(&valves, "AA");
+    Some(cave.best_pressure(30))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (_, valves) = valves_parser(input).unwrap();
-    let valves_map: FxHashMap<&str, &Valve> = valves.iter().map(|v| (v.name, v)).collect();
-    Some(find_with_elephant("AA", 26, &valves_map))
-}
-
-#[derive(Clone, Eq, PartialEq, Debug)]
-struct ToVisit<'a> {
-    valve: &'a str,
-    open_valves: FxHashSet<&'a str>,
-    throughput: u64,
-    turns: u32,
-}
-
-fn total_flow(open_valves: &FxHashSet<&str>, map: &FxHashMap<&str, &Valve>) -> u64 {
-    open_valves.iter().map(|v| map[v].flow_rate).sum()
-}
+    let valves = parse_all(valves_parser, input).unwrap();
+    let cave = Cave::new(&valves, "AA");
+    let mut pressures: Vec<(BitSet, u64)> = cave.best_pressures(26).into_iter().collect();
+    pressures.sort_by_key(|(_, pressure)| std::cmp::Reverse(*pressure));
 
-fn can_open_valve(
-    valve: &str,
-    open_valves: &FxHashSet<&str>,
-    map: &FxHashMap<&str, &Valve>,
-) -> bool {
-    map[valve].flow_rate > 0 && !open_valves.contains(valve)
-}
-
-fn find_best_throughput(start: &str, turns_limit: u32, map: &FxHashMap<&str, &Valve>) -> u64 {
-    let mut visited: FxHashMap<(&str, u32), u64> = FxHashMap::default();
-    let mut to_visit = Vec::new();
+    // the elephant and I open disjoint sets of valves, the empty set being one of them.
     let mut best = 0;
-
-    to_visit.push(ToVisit {
-        valve: start,
-        open_valves: FxHashSet::default(),
-        throughput: 0,
-        turns: 1,
-    });
-
-    while let Some(cur) = to_visit.pop() {
-        if let Some(v) = visited.get(&(cur.valve, cur.turns)) {
-            if v >= &cur.throughput {
-                continue;
+    for (index, (mine, my_pressure)) in pressures.iter().enumerate() {
+        for (theirs, their_pressure) in pressures[index..].iter() {
+            if my_pressure + their_pressure <= best {
+                break;
+            }
+            if mine.is_disjoint(*theirs) {
+                best = my_pressure + their_pressure;
             }
         }
-
-        visited.insert((cur.valve, cur.turns), cur.throughput);
-
-        if cur.turns == turns_limit {
-            best = std::cmp::max(best, cur.throughput);
-            continue;
-        }
-
-        if can_open_valve(cur.valve, &cur.open_valves, map) {
-            let mut new_valves = cur.open_valves.clone();
-            new_valves.insert(cur.valve);
-
-            to_visit.push(ToVisit {
-                valve: cur.valve,
-                throughput: cur.throughput + total_flow(&new_valves, map),
-                open_valves: new_valves,
-                turns: cur.turns + 1,
-            });
-        }
-
-        for next in map[cur.valve].leads_to.iter() {
-            to_visit.push(ToVisit {
-                valve: next,
-                throughput: cur.throughput + total_flow(&cur.open_valves, map),
-                open_valves: cur.open_valves.clone(),
-                turns: cur.turns + 1,
-            });
+    }
+    Some(best)
+}
+
+// only the start and the valves worth opening, with the minutes it takes to walk between them.
+struct Cave {
+    distances: Table<(usize, usize), Option<u32>>,
+    flow_rates: Vec<u64>,
+    start: usize,
+}
+
+impl Cave {
+    fn new(valves: &[Valve], start: &str) -> Self {
+        let flow_rates: FxHashMap<&str, u64> =
+            valves.iter().map(|v| (v.name, v.flow_rate)).collect();
+        let tunnels = Graph::from_adjacency(valves.iter().map(|v| (v.name, v.leads_to.clone())))
+            .contract(|name| *name == start || flow_rates[name] > 0);
+
+        // the open valves of every search state fit in a single word.
+        assert!(
+            tunnels.labels().len() <= BitSet::<u64>::CAPACITY,
+            "{} valves are too many to open",
+            tunnels.labels().len()
+        );
+
+        Cave {
+            distances: tunnels.all_pairs(),
+            flow_rates: tunnels
+                .labels()
+                .iter()
+                .map(|name| flow_rates[name])
+                .collect(),
+            start: tunnels.id(&start).unwrap(),
         }
     }
 
-    best
-}
-
-#[derive(Clone, Eq, PartialEq, Debug)]
-struct WithElephant<'a> {
-    me: &'a str,
-    elephant: &'a str,
-    open_valves: FxHashSet<&'a str>,
-    throughput: u64,
-    turns: u32,
-}
-
-fn both_move<'a>(
-    cur: WithElephant<'a>,
-    map: &'a FxHashMap<&str, &Valve>,
-    to_visit: &mut Vec<WithElephant<'a>>,
-) {
-    for next_me in map[cur.me].leads_to.iter() {
-        for next_elephant in map[cur.elephant].leads_to.iter() {
-            if next_me != next_elephant {
-                to_visit.push(WithElephant {
-                    me: next_me,
-                    elephant: next_elephant,
-                    throughput: cur.throughput + total_flow(&cur.open_valves, map),
-                    open_valves: cur.open_valves.clone(),
-                    turns: cur.turns + 1,
-                });
-            }
-        }
+    // the valves still worth opening from `valve`, with the minutes it takes to walk
+    // there and open them.
+    fn closed_valves(
+        &self,
+        valve: usize,
+        minutes: u32,
+        open: BitSet,
+    ) -> impl Iterator<Item = (usize, u32)> + '_ {
+        (0..self.flow_rates.len())
+            .filter(move |next| self.flow_rates[*next] > 0 && !open.contains(*next))
+            .filter_map(move |next| Some((next, self.distances[(valve, next)]? + 1)))
+            .filter(move |(_, cost)| *cost < minutes)
     }
-}
 
-fn both_open_valve<'a>(
-    cur: WithElephant<'a>,
-    map: &'a FxHashMap<&str, &Valve>,
-    to_visit: &mut Vec<WithElephant<'a>>,
-) {
-    let mut new_valves = cur.open_valves.clone();
-    new_valves.insert(cur.me);
-    new_valves.insert(cur.elephant);
+    /// The most pressure released within `minutes`, skipping paths that can't beat the best one.
+    fn best_pressure(&self, minutes: u32) -> u64 {
+        let mut best = 0;
+        let mut to_visit = vec![(self.start, minutes, BitSet::new(), 0)];
 
-    to_visit.push(WithElephant {
-        me: cur.me,
-        elephant: cur.elephant,
-        throughput: cur.throughput + total_flow(&new_valves, map),
-        open_valves: new_valves,
-        turns: cur.turns + 1,
-    })
-}
+        while let Some((valve, minutes, open, pressure)) = to_visit.pop() {
+            best = best.max(pressure);
 
-fn i_open_valve<'a>(
-    cur: WithElephant<'a>,
-    map: &'a FxHashMap<&str, &Valve>,
-    to_visit: &mut Vec<WithElephant<'a>>,
-) {
-    let mut new_valves = cur.open_valves.clone();
-    new_valves.insert(cur.me);
+            let closed: Vec<(usize, u32)> = self.closed_valves(valve, minutes, open).collect();
 
-    for next_elephant in map[cur.elephant].leads_to.iter() {
-        to_visit.push(WithElephant {
-            me: cur.me,
-            elephant: next_elephant,
-            throughput: cur.throughput + total_flow(&new_valves, map),
-            open_valves: new_valves.clone(),
-            turns: cur.turns + 1,
-        });
-    }
-}
+            // as if every valve left could be opened right after walking straight to it.
+            let bound: u64 = closed
+                .iter()
+                .map(|(next, cost)| self.flow_rates[*next] * (minutes - cost) as u64)
+                .sum();
+            if pressure + bound <= best {
+                continue;
+            }
 
-fn elephant_open_valve<'a>(
-    cur: WithElephant<'a>,
-    map: &'a FxHashMap<&str, &Valve>,
-    to_visit: &mut Vec<WithElephant<'a>>,
-) {
-    let mut new_valves = cur.open_valves.clone();
-    new_valves.insert(cur.elephant);
+            for (next, cost) in closed {
+                let left = minutes - cost;
+                let released = self.flow_rates[next] * left as u64;
+                to_visit.push((next, left, open.with(next), pressure + released));
+            }
+        }
 
-    for next_me in map[cur.me].leads_to.iter() {
-        to_visit.push(WithElephant {
-            me: next_me,
-            elephant: cur.elephant,
-            throughput: cur.throughput + total_flow(&new_valves, map),
-            open_valves: new_valves.clone(),
-            turns: cur.turns + 1,
-        });
+        best
     }
-}
-
-fn find_with_elephant(start: &str, turns_limit: u32, map: &FxHashMap<&str, &Valve>) -> u64 {
-    let mut visited: FxHashMap<(&str, &str, u32), u64> = FxHashMap::default();
-    let mut to_visit = Vec::new();
-    let mut best = 0;
 
-    to_visit.push(WithElephant {
-        me: start,
-        elephant: start,
-        open_valves: FxHashSet::default(),
-        throughput: 0,
-        turns: 1,
-    });
+    /// The most pressure released by opening each reachable set of valves within `minutes`.
+    fn best_pressures(&self, minutes: u32) -> FxHashMap<BitSet, u64> {
+        let mut result: FxHashMap<BitSet, u64> = FxHashMap::default();
+        // the best pressure seen at a valve, with the same minutes left and open valves.
+        let mut visited: FxHashMap<(usize, u32, BitSet), u64> = FxHashMap::default();
+        let mut to_visit = vec![(self.start, minutes, BitSet::new(), 0)];
 
-    while let Some(cur) = to_visit.pop() {
-        if let Some(v) = visited.get(&(cur.me, cur.elephant, cur.turns)) {
-            if v >= &cur.throughput {
+        while let Some((valve, minutes, open, pressure)) = to_visit.pop() {
+            let key = (valve, minutes, open);
+            if visited.get(&key).is_some_and(|seen| *seen >= pressure) {
                 continue;
             }
-        }
-
-        visited.insert((cur.me, cur.elephant, cur.turns), cur.throughput);
+            visited.insert(key, pressure);
 
-        if cur.turns == turns_limit {
-            best = std::cmp::max(best, cur.throughput);
-            continue;
-        }
-
-        // if all valves are open we can stop exploring
-        if cur.open_valves.len() == map.values().filter(|v| v.flow_rate > 0).count() {
-            to_visit.push(WithElephant {
-                me: cur.me,
-                elephant: cur.elephant,
-                open_valves: cur.open_valves.clone(),
-                throughput: cur.throughput
-                    + ((turns_limit - cur.turns) as u64) * total_flow(&cur.open_valves, map),
-                turns: turns_limit,
-            });
-            continue;
-        }
+            let best = result.entry(open).or_default();
+            *best = pressure.max(*best);
 
-        match (
-            can_open_valve(cur.me, &cur.open_valves, map),
-            can_open_valve(cur.elephant, &cur.open_valves, map),
-        ) {
-            (true, true) => {
-                both_open_valve(cur.clone(), map, &mut to_visit);
-                i_open_valve(cur.clone(), map, &mut to_visit);
-                elephant_open_valve(cur.clone(), map, &mut to_visit);
-                both_move(cur, map, &mut to_visit)
-            }
-            (true, false) => {
-                i_open_valve(cur.clone(), map, &mut to_visit);
-                both_move(cur, map, &mut to_visit);
+            for (next, cost) in self.closed_valves(valve, minutes, open) {
+                let left = minutes - cost;
+                let released = self.flow_rates[next] * left as u64;
+                to_visit.push((next, left, open.with(next), pressure + released));
             }
-            (false, true) => {
-                elephant_open_valve(cur.clone(), map, &mut to_visit);
-                both_move(cur, map, &mut to_visit);
-            }
-            (false, false) => both_move(cur, map, &mut to_visit),
         }
-    }
 
-    best
+        result
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 16);
+    let input = &advent_of_code::read_input(16);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -246,30 +151,17 @@ struct Valve<'a> {
     leads_to: Vec<&'a str>,
 }
 
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, digit1},
-    combinator::{all_consuming, map, opt},
-    multi::separated_list1,
-    sequence::{terminated, tuple},
-    IResult,
-};
-
-fn valves_parser(i: &str) -> IResult<&str, Vec<Valve>> {
-    all_consuming(terminated(
-        separated_list1(tag("\n"), valve_parser),
-        opt(tag("\n")),
-    ))(i)
+fn valves_parser(i: &str) -> IResult<&str, Vec<Valve<'_>>> {
+    lines(valve_parser)(i)
 }
 
-fn valve_parser(i: &str) -> IResult<&str, Valve> {
+fn valve_parser(i: &str) -> IResult<&str, Valve<'_>> {
     map(
         tuple((
             tag("Valve "),
             alphanumeric1,
             tag(" has flow rate="),
-            digit1,
+            uint,
             alt((
                 tag("; tunnels lead to valves "),
                 tag("; tunnel leads to valve "),
@@ -278,7 +170,7 @@ fn valve_parser(i: &str) -> IResult<&str, Valve> {
         )),
         |(_, name, _, flow_rate, _, leads_to)| Valve {
             name,
-            flow_rate: flow_rate.parse().unwrap(),
+            flow_rate,
             leads_to,
         },
     )(i)
@@ -291,7 +183,7 @@ mod tests {
     #[test]
     fn test_parse() {
         let input = advent_of_code::read_file("examples", 16);
-        assert_eq!(valves_parser(&input).unwrap().0, "");
+        assert!(parse_all(valves_parser, &input).is_ok());
     }
 
     #[test]
@@ -300,9 +192,9 @@ mod tests {
         assert_eq!(part_one(&input), Some(1651));
     }
 
-    // #[test]
-    // fn test_part_two() {
-    //     let input = advent_of_code::read_file("examples", 16);
-    //     assert_eq!(part_two(&input), Some(1707));
-    // }
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 16);
+        assert_eq!(part_two(&input), Some(1707));
+    }
 }
@@ -1,103 +1,91 @@
-use rustc_hash::{FxHashMap, FxHashSet};
+use advent_of_code::helpers::{cycle, BitSet, Dir4, Grid, Point2};
+use rustc_hash::FxHashSet;
 use std::fmt;
 use std::str::FromStr;
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let shapes = get_all_shapes();
-    let directions = parse_input(input);
-    let mut direction_source = directions.iter().cycle();
-
-    let mut board = Board::new();
-    let mut counter = 0;
-    let threshold = 2022;
-
-    for current_shape in shapes.iter().cycle() {
-        counter += 1;
-        if counter > threshold {
-            break;
-        }
-
-        board.add_shape(current_shape.clone());
-
-        for dir in direction_source.by_ref() {
-            board.move_shape(*dir, 1);
-
-            if board.move_shape(Direction::Down, 1) {
-                continue;
-            } else {
-                board.settle_shape();
-                break;
-            }
-        }
+    let mut tower = Tower::new(parse_input(input));
+    for _ in 0..2022 {
+        tower.drop_rock();
     }
 
-    Some(board.height())
+    Some(tower.board.height())
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let shapes = get_all_shapes();
-    let directions = parse_input(input);
-    let mut direction_source = directions.iter().enumerate().cycle();
+    let height = height_after(parse_input(input), 1_000_000_000_000);
+    if height.is_none() {
+        // e.g. when the jets never push rocks into a column, which stays open down to the floor.
+        eprintln!("the top of the tower never repeats itself");
+    }
+    height
+}
 
-    let mut memory: FxHashMap<(i64, usize, usize), (u64, i64)> = FxHashMap::default();
+// the height of the tower after `rocks` rocks, `None` if it doesn't settle into a cycle.
+fn height_after(jets: Vec<Dir4>, rocks: usize) -> Option<i64> {
+    let tower = Tower::new(jets);
+    // every shape meets every jet within that many rocks, a cycle being a few of those rounds.
+    let budget = 10 * tower.shapes.len() * tower.jets.len();
+
+    // the same surface with the same next shape and jet means we are in a cycle.
+    cycle::extrapolate(
+        tower,
+        |tower| tower.drop_rock(),
+        |tower| (tower.board.surface(), tower.shape_index, tower.jet_index),
+        |tower| tower.board.height(),
+        rocks,
+        budget,
+    )
+}
+
+fn main() {
+    let input = &advent_of_code::read_input(17);
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
 
-    let mut board = Board::new();
-    let mut counter: u64 = 0;
-    let threshold: u64 = 1_000_000_000_000;
+struct Tower {
+    board: Board,
+    shapes: Vec<Shape>,
+    jets: Vec<Dir4>,
+    shape_index: usize,
+    jet_index: usize,
+}
 
-    for (shape_index, current_shape) in shapes.iter().enumerate().cycle() {
-        counter += 1;
-        if counter > threshold {
-            break;
+impl Tower {
+    fn new(jets: Vec<Dir4>) -> Self {
+        Self {
+            board: Board::new(),
+            shapes: get_all_shapes(),
+            jets,
+            shape_index: 0,
+            jet_index: 0,
         }
+    }
 
-        board.add_shape(current_shape.clone());
-
-        for (dir_index, dir) in direction_source.by_ref() {
-            board.move_shape(*dir, 1);
-
-            if board.move_shape(Direction::Down, 1) {
-                continue;
-            } else {
-                board.settle_shape();
-
-                let board_hash = board.hash(8);
-
-                // if we have a similar board with the same shape and same direction,
-                // we have detected a cycle and can skip a lot of steps
-                if let Some((old_counter, old_height)) =
-                    memory.get(&(board_hash, shape_index, dir_index))
-                {
-                    let counter_diff = counter - old_counter;
-                    let height_diff = board.height() - old_height;
-                    let remaining = (threshold - counter) / counter_diff;
-                    counter += remaining * counter_diff;
-                    board.floor_level += remaining as i64 * height_diff;
-                } else {
-                    memory.insert(
-                        (board_hash, shape_index, dir_index),
-                        (counter, board.height()),
-                    );
-                }
+    fn drop_rock(&mut self) {
+        let shape = self.shapes[self.shape_index].clone();
+        self.shape_index = (self.shape_index + 1) % self.shapes.len();
+        self.board.add_shape(shape);
 
+        loop {
+            let jet = self.jets[self.jet_index];
+            self.jet_index = (self.jet_index + 1) % self.jets.len();
+            self.board.move_shape(jet, 1);
+
+            if !self.board.move_shape(Dir4::Down, 1) {
+                self.board.settle_shape();
                 break;
             }
         }
     }
-
-    Some(board.height())
-}
-
-fn main() {
-    let input = &advent_of_code::read_file("inputs", 17);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
 }
 
 #[derive(Debug, PartialEq)]
 struct Board {
     current: Option<Shape>,
-    points: FxHashSet<Pos>,
+    // the columns taken in each row, the floor being row 0.
+    rows: Vec<BitSet<u8>>,
     floor_level: i64,
     max_y: i64, // cache height for performance
 }
@@ -106,30 +94,30 @@ impl Board {
     pub fn new() -> Self {
         Self {
             current: None,
-            points: (0..7).map(|x| Pos { x, y: 0 }).collect(),
+            rows: vec![(0..7).collect()],
             floor_level: 0,
             max_y: 0,
         }
     }
 
+    fn is_rock(&self, pos: Pos) -> bool {
+        usize::try_from(pos.y)
+            .ok()
+            .and_then(|y| self.rows.get(y))
+            .is_some_and(|row| row.contains(pos.x as usize))
+    }
+
     fn add_shape(&mut self, shape: Shape) {
-        self.current = Some(
-            shape
-                .shift(Direction::Right, 2)
-                .shift(Direction::Up, self.max_y + 4),
-        );
+        self.current = Some(shape.shift(Dir4::Right, 2).shift(Dir4::Up, self.max_y + 4));
     }
 
-    fn move_shape(&mut self, dir: Direction, count: i64) -> bool {
+    fn move_shape(&mut self, dir: Dir4, count: i64) -> bool {
         if let Some(current) = &self.current {
             let next_position = current.shift(dir, count);
             let min_x = next_position.points.iter().map(|p| p.x).min().unwrap();
             let max_x = next_position.points.iter().map(|p| p.x).max().unwrap();
 
-            if min_x >= 0
-                && max_x < 7
-                && self.points.intersection(&next_position.points).count() == 0
-            {
+            if min_x >= 0 && max_x < 7 && !next_position.points.iter().any(|p| self.is_rock(*p)) {
                 self.current = Some(next_position);
                 return true;
             }
@@ -142,7 +130,11 @@ impl Board {
         if let Some(current) = &self.current {
             let mut max_y = self.max_y;
             for point in &current.points {
-                self.points.insert(point.clone());
+                let y = point.y as usize;
+                if y >= self.rows.len() {
+                    self.rows.resize(y + 1, BitSet::new());
+                }
+                self.rows[y].insert(point.x as usize);
                 max_y = max_y.max(point.y);
             }
             self.current = None;
@@ -155,45 +147,42 @@ impl Board {
     fn shrink(&mut self) {
         let result: Option<i64> = (1..=self.max_y)
             .rev()
-            .find(|y| (0..7).all(|x| self.points.get(&Pos { x, y: *y }).is_some()));
+            .find(|y| self.rows[*y as usize].len() == 7);
         if let Some(high_y) = result {
-            self.points = self
-                .points
-                .iter()
-                .filter_map(|pos| {
-                    if pos.y >= high_y {
-                        let difference = pos.y - high_y;
-                        Some(Pos {
-                            x: pos.x,
-                            y: difference,
-                        })
-                    } else {
-                        None
-                    }
-                })
-                .collect();
+            // the full row becomes the new floor.
+            self.rows.drain(..high_y as usize);
 
             self.floor_level += high_y;
             self.max_y -= high_y;
         }
     }
 
-    fn hash(&self, top_rows: i64) -> i64 {
-        let min_y = self.max_y - (top_rows - 1);
-        let bools: Vec<bool> = (min_y..=self.max_y)
-            .flat_map(|y| {
-                (0..7)
-                    .map(|x| self.points.get(&Pos { x, y }).is_some())
-                    .collect::<Vec<_>>()
-            })
-            .collect();
+    // the empty cells that falling rocks can still reach, row by row from the top one down.
+    // rocks only move sideways and down, so the cells below can never matter again.
+    fn surface(&self) -> Vec<BitSet<u8>> {
+        let mut result = vec![];
+        let mut above: BitSet<u8> = (0..7).collect();
+
+        for row in self.rows[..=self.max_y as usize].iter().rev() {
+            let empty = BitSet::from_bits(!row.bits() & 0x7f);
+            let mut reached = above & empty;
+            loop {
+                let bits = reached.bits();
+                let spread = BitSet::from_bits(bits | bits << 1 | bits >> 1) & empty;
+                if spread == reached {
+                    break;
+                }
+                reached = spread;
+            }
 
-        bools
-            .iter()
-            .rev()
-            .enumerate()
-            .map(|(i, v)| if *v { 2_i64.pow(i as u32) } else { 0 })
-            .sum()
+            if reached.is_empty() {
+                break;
+            }
+            result.push(reached);
+            above = reached;
+        }
+
+        result
     }
 
     fn height(&self) -> i64 {
@@ -228,7 +217,7 @@ impl fmt::Display for Board {
                             .is_some()
                         {
                             '@'
-                        } else if self.points.get(&cur_pos).is_some() {
+                        } else if self.is_rock(cur_pos) {
                             '#'
                         } else {
                             '.'
@@ -243,30 +232,16 @@ impl fmt::Display for Board {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Direction {
-    Right,
-    Left,
-    Down,
-    Up,
-}
-
-fn parse_input(input: &str) -> Vec<Direction> {
+// jets only push rocks sideways.
+fn parse_input(input: &str) -> Vec<Dir4> {
     input
         .chars()
-        .filter_map(|c| match c {
-            '>' => Some(Direction::Right),
-            '<' => Some(Direction::Left),
-            _ => None,
-        })
+        .filter_map(Dir4::from_char)
+        .filter(|dir| dir.is_horizontal())
         .collect()
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Pos {
-    x: i64,
-    y: i64,
-}
+type Pos = Point2<i64>;
 
 #[derive(Debug, PartialEq, Clone)]
 struct Shape {
@@ -280,41 +255,30 @@ impl FromStr for Shape {
     type Err = ParseShapeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result: FxHashSet<Pos> = FxHashSet::default();
-
-        for (y, line) in s.lines().rev().enumerate() {
-            for (x, value) in line.chars().enumerate() {
-                if value == '#' {
-                    result.insert(Pos {
-                        x: x as i64,
-                        y: y as i64,
-                    });
-                }
-            }
-        }
+        let grid = Grid::parse(s, |c| c == '#');
+
+        // rows are listed top to bottom, but y grows upwards.
+        let points = grid
+            .iter()
+            .filter(|(_, rock)| **rock)
+            .map(|((x, y), _)| Pos {
+                x: x as i64,
+                y: (grid.height() - 1 - y) as i64,
+            })
+            .collect();
 
-        Ok(Shape { points: result })
+        Ok(Shape { points })
     }
 }
 
 impl Shape {
-    fn shift(&self, dir: Direction, count: i64) -> Self {
-        let (step_x, step_y) = match dir {
-            Direction::Right => (count, 0),
-            Direction::Left => (-count, 0),
-            Direction::Down => (0, -count),
-            Direction::Up => (0, count),
-        };
+    fn shift(&self, dir: Dir4, count: i64) -> Self {
+        // y grows upwards, away from the floor.
+        let step = dir.step_y_up();
+        let step = Pos::new(step.x as i64, step.y as i64) * count;
 
         Shape {
-            points: self
-                .points
-                .iter()
-                .map(|Pos { x, y }| Pos {
-                    x: *x + step_x,
-                    y: *y + step_y,
-                })
-                .collect(),
+            points: self.points.iter().map(|pos| *pos + step).collect(),
         }
     }
 }
@@ -348,7 +312,7 @@ fn get_all_shapes() -> Vec<Shape> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Direction::*;
+    use crate::Dir4::*;
 
     #[test]
     fn test_parse_directions() {
@@ -399,15 +363,40 @@ mod tests {
         );
     }
 
-    // #[test]
-    // fn test_part_one() {
-    //     let input = advent_of_code::read_file("examples", 17);
-    //     assert_eq!(part_one(&input), Some(3068));
-    // }
-
-    // #[test]
-    // fn test_part_two() {
-    //     let input = advent_of_code::read_file("examples", 17);
-    //     assert_eq!(part_two(&input), Some(1514285714288));
-    // }
+    #[test]
+    fn test_surface() {
+        let mut board = Board::new();
+        // a ledge over an empty cell, which rocks can still slide under from the side.
+        board.rows.push([0, 1, 2, 3].into_iter().collect());
+        board.rows.push([0, 1, 2, 3, 4, 5].into_iter().collect());
+        board.max_y = 2;
+        let cells = |xs: &[usize]| xs.iter().copied().collect::<BitSet<u8>>();
+        assert_eq!(board.surface(), [cells(&[6]), cells(&[4, 5, 6])]);
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_cycle_property() {
+        advent_of_code::generators::check_property(17, 60, |input| {
+            let mut tower = Tower::new(parse_input(input));
+            for _ in 0..1000 {
+                tower.drop_rock();
+            }
+            if let Some(height) = height_after(parse_input(input), 1000) {
+                assert_eq!(height, tower.board.height());
+            }
+        });
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 17);
+        assert_eq!(part_one(&input), Some(3068));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 17);
+        assert_eq!(part_two(&input), Some(1514285714288));
+    }
 }
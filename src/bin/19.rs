@@ -1,4 +1,4 @@
-use parse_display::{Display, FromStr};
+use advent_of_code::helpers::ints;
 use rayon::iter::IntoParallelIterator;
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
@@ -26,13 +26,12 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 19);
+    let input = &advent_of_code::read_input(19);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[derive(Display, FromStr, PartialEq, Debug, Clone, Copy)]
-#[display("Blueprint {id}: Each ore robot costs {ore_robot_ore} ore. Each clay robot costs {clay_robot_ore} ore. Each obsidian robot costs {obsidian_robot_ore} ore and {obsidian_robot_clay} clay. Each geode robot costs {geode_robot_ore} ore and {geode_robot_obsidian} obsidian.")]
+#[derive(PartialEq, Debug, Clone, Copy)]
 struct BlueprintInfo {
     id: u32,
     ore_robot_ore: u32,
@@ -47,7 +46,19 @@ fn parse_input(input: &str) -> Vec<BlueprintInfo> {
     input
         .trim()
         .lines()
-        .map(|line| line.parse().unwrap())
+        .map(|line| {
+            let [id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+                ints(line).unwrap();
+            BlueprintInfo {
+                id,
+                ore_robot_ore: ore,
+                clay_robot_ore: clay,
+                obsidian_robot_ore: obsidian_ore,
+                obsidian_robot_clay: obsidian_clay,
+                geode_robot_ore: geode_ore,
+                geode_robot_obsidian: geode_obsidian,
+            }
+        })
         .collect()
 }
 
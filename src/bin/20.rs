@@ -1,31 +1,62 @@
+use advent_of_code::helpers::checked::{Checked, Overflow};
+use advent_of_code::helpers::num::Integer;
+use advent_of_code::helpers::IndexedList;
 use std::collections::VecDeque;
 
+const DECRYPTION_KEY: i64 = 811589153;
+
 pub fn part_one(input: &str) -> Option<i64> {
     let initial = parse_input(input);
-    let mut mixed = mix_sequence(initial, 1);
-    find_solution(&mut mixed)
+    decrypt::<i64>(&initial, 1, 1)
+        .inspect_err(|overflow| eprintln!("{}", overflow))
+        .ok()
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let initial = parse_input(input).iter().map(|v| v * 811589153).collect();
-    let mut mixed = mix_sequence(initial, 10);
+    let initial = parse_input(input);
+    // numbers times the key may not fit in `i64` when they are slightly larger than usual,
+    // even if the sum of the three we pick does.
+    let sum = decrypt::<i64>(&initial, DECRYPTION_KEY, 10)
+        .map(i128::from)
+        .or_else(|_| decrypt::<i128>(&initial, DECRYPTION_KEY, 10));
+
+    match sum {
+        Ok(sum) => i64::try_from(sum)
+            .inspect_err(|_| eprintln!("the answer {} does not fit in i64", sum))
+            .ok(),
+        Err(overflow) => {
+            eprintln!("{}", overflow);
+            None
+        }
+    }
+}
+
+fn decrypt<T: Integer + From<i64> + Into<i128>>(
+    initial: &VecDeque<i64>,
+    key: i64,
+    rounds: u8,
+) -> Result<T, Overflow<T>> {
+    let keyed = initial
+        .iter()
+        .map(|v| (Checked::new(T::from(*v)) * T::from(key)).get())
+        .collect::<Result<_, _>>()?;
+    let mut mixed = mix_sequence(keyed, rounds);
     find_solution(&mut mixed)
 }
 
-fn find_solution(nums: &mut VecDeque<i64>) -> Option<i64> {
-    let zero_pos = nums.iter().position(|v| *v == 0).unwrap();
+fn find_solution<T: Integer>(nums: &mut VecDeque<T>) -> Result<T, Overflow<T>> {
+    let zero_pos = nums.iter().position(|v| *v == T::ZERO).unwrap();
     nums.rotate_left(zero_pos);
 
-    Some(
-        [1000, 2000, 3000]
-            .iter()
-            .map(|i| nums[i % nums.len()])
-            .sum(),
-    )
+    [1000, 2000, 3000]
+        .iter()
+        .map(|i| Checked::new(nums[i % nums.len()]))
+        .sum::<Checked<T>>()
+        .get()
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 20);
+    let input = &advent_of_code::read_input(20);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -34,30 +65,25 @@ fn parse_input(input: &str) -> VecDeque<i64> {
     input.lines().filter_map(|line| line.parse().ok()).collect()
 }
 
-fn mix_sequence(input: VecDeque<i64>, times: u8) -> VecDeque<i64> {
-    let len = input.len() as i64;
-    let mut result = VecDeque::from_iter(input.iter().copied().enumerate());
+fn mix_sequence<T: Integer + Into<i128>>(input: VecDeque<T>, times: u8) -> VecDeque<T> {
+    let len = input.len() as i128;
+    let mut result = IndexedList::new();
+    let handles: Vec<_> = input.iter().map(|value| result.push(*value)).collect();
 
     for _ in 0..times {
-        for (id, value) in input.iter().enumerate() {
-            if *value == 0 {
+        for (handle, value) in handles.iter().zip(input.iter()) {
+            if *value == T::ZERO {
                 continue;
             }
 
-            let idx = result.iter().position(|(r_id, _)| *r_id == id).unwrap();
-            result.remove(idx);
-
-            let new_idx = (idx as i64 + value) % (len - 1);
-            let abs_idx = if new_idx < 0 {
-                (len - 1 + new_idx) % (len - 1)
-            } else {
-                new_idx
-            };
-            result.insert(abs_idx as usize, (id, *value));
+            // the other numbers make a circle of len - 1 slots.
+            let idx = result.index_of(*handle).unwrap();
+            let new_idx = (idx as i128 + (*value).into()).rem_euclid(len - 1);
+            result.move_to(*handle, new_idx as usize);
         }
     }
 
-    result.iter().map(|(_, v)| *v).collect()
+    result.iter().copied().collect()
 }
 
 #[cfg(test)]
@@ -81,4 +107,62 @@ mod tests {
         let input = advent_of_code::read_file("examples", 20);
         assert_eq!(part_two(&input), Some(1623178306));
     }
+
+    #[test]
+    fn test_wide_numbers() {
+        // 20000000000 times the key is larger than i64::MAX.
+        let input = "1\n20000000000\n-3\n3\n-2\n0\n4\n";
+        let initial = parse_input(input);
+        assert!(decrypt::<i64>(&initial, DECRYPTION_KEY, 10).is_err());
+        let expected = decrypt::<i128>(&initial, DECRYPTION_KEY, 10).unwrap();
+        assert_eq!(part_two(input), i64::try_from(expected).ok());
+    }
+
+    // moves every number one swap at a time, slow but hard to get wrong.
+    #[cfg(feature = "generators")]
+    fn naive_mix(nums: &[i64], times: u8) -> Vec<i64> {
+        let len = nums.len();
+        let mut order: Vec<usize> = (0..len).collect();
+
+        for _ in 0..times {
+            for (id, num) in nums.iter().enumerate() {
+                let mut pos = order.iter().position(|o| *o == id).unwrap();
+                for _ in 0..num.rem_euclid(len as i64 - 1) {
+                    let next = (pos + 1) % len;
+                    order.swap(pos, next);
+                    pos = next;
+                }
+            }
+        }
+
+        order.iter().map(|id| nums[*id]).collect()
+    }
+
+    #[cfg(feature = "generators")]
+    fn from_zero(nums: impl IntoIterator<Item = i64>) -> VecDeque<i64> {
+        let mut nums: VecDeque<i64> = nums.into_iter().collect();
+        let zero_pos = nums.iter().position(|v| *v == 0).unwrap();
+        nums.rotate_left(zero_pos);
+        nums
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_naive_mix_property() {
+        advent_of_code::generators::check_property(20, 30, |input| {
+            let initial = parse_input(input);
+            let naive = naive_mix(&Vec::from(initial.clone()), 1);
+            assert_eq!(from_zero(mix_sequence(initial, 1)), from_zero(naive));
+        });
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_naive_mix_with_key_property() {
+        advent_of_code::generators::check_property(20, 30, |input| {
+            let initial: VecDeque<i64> = parse_input(input).iter().map(|v| v * 811589153).collect();
+            let naive = naive_mix(&Vec::from(initial.clone()), 10);
+            assert_eq!(from_zero(mix_sequence(initial, 10)), from_zero(naive));
+        });
+    }
 }
@@ -1,24 +1,28 @@
-use std::cell::RefCell;
+use advent_of_code::helpers::memo::{CycleError, Memo, MemoResult};
+use advent_of_code::helpers::num::gcd;
+use advent_of_code::helpers::parse::{int, lines, parse_all};
 use std::collections::HashMap;
+use std::fmt;
 
-type InsMap<'a> = HashMap<&'a str, RefCell<Value<'a>>>;
+type InsMap<'a> = HashMap<&'a str, Value<'a>>;
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let (_, ins) = input_parser(input).unwrap();
-    let map: InsMap = ins.iter().map(|i| (i.res, RefCell::new(i.val))).collect();
-    Some(calculate_field("root", &map))
+    let ins = parse_all(input_parser, input).unwrap();
+    let map: InsMap = ins.iter().map(|i| (i.res, i.val)).collect();
+    let mut fields = Memo::new(|fields, field| calculate_field(fields, field, &map));
+    Some(fields.get("root").unwrap())
 }
 
-use std::process::Command;
-
-pub fn part_two(path: &str) -> Option<i64> {
-    let output = match Command::new("src/bin/21.sh").args([path]).output() {
-        Ok(output) => String::from_utf8(output.stdout).unwrap(),
-        Err(err) => panic!("Running process error: {}", err),
-    };
-
-    let res: f64 = output.parse().unwrap();
-    Some(res.round() as i64)
+pub fn part_two(input: &str) -> Option<i64> {
+    let ins = parse_all(input_parser, input).unwrap();
+    let map: InsMap = ins.iter().map(|i| (i.res, i.val)).collect();
+    match solve_humn(&map) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            eprintln!("{}", err);
+            None
+        }
+    }
 }
 
 fn run_op(fst: i64, op: Op, snd: i64) -> i64 {
@@ -30,27 +34,193 @@ fn run_op(fst: i64, op: Op, snd: i64) -> i64 {
     }
 }
 
-fn calculate_field<'a>(field: &'a str, map: &InsMap) -> i64 {
-    let result = match *map[field].borrow() {
-        Value::Lit(v) => v,
-        Value::Expr((fst, op, snd)) => {
-            let x = calculate_field(fst, map);
-            let y = calculate_field(snd, map);
-            run_op(x, op, y)
+fn calculate_field<'a>(
+    fields: &mut dyn FnMut(&'a str) -> MemoResult<&'a str, i64>,
+    field: &'a str,
+    map: &InsMap<'a>,
+) -> MemoResult<&'a str, i64> {
+    match map[field] {
+        Value::Lit(v) => Ok(v),
+        Value::Expr((fst, op, snd)) => Ok(run_op(fields(fst)?, op, fields(snd)?)),
+    }
+}
+
+const HUMN: &str = "humn";
+
+#[derive(Debug, PartialEq)]
+enum SolveError<'a> {
+    /// `humn` is on both sides of `root`.
+    BothSides,
+    /// `humn` is on neither side of `root`, or `root` is not an operation.
+    NoUnknown,
+    /// `humn` appears in both operands of this monkey, as in `humn * humn`.
+    NonLinear(&'a str),
+    /// No single value works at this monkey, e.g. when multiplying by zero.
+    NoSolution(&'a str),
+    /// The only solution is this fraction.
+    NotInteger(Ratio),
+    /// This monkey ends up waiting for its own number.
+    Cycle(&'a str),
+}
+
+impl fmt::Display for SolveError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::BothSides => write!(f, "{} is on both sides of root", HUMN),
+            SolveError::NoUnknown => write!(f, "{} is on neither side of root", HUMN),
+            SolveError::NonLinear(field) => {
+                write!(f, "{} appears in both operands of {}", HUMN, field)
+            }
+            SolveError::NoSolution(field) => {
+                write!(f, "no single value of {} solves {}", HUMN, field)
+            }
+            SolveError::NotInteger(value) => {
+                write!(f, "{} should be {}, not an integer", HUMN, value)
+            }
+            SolveError::Cycle(field) => write!(f, "{} depends on itself", field),
         }
+    }
+}
+
+impl<'a> From<CycleError<&'a str>> for SolveError<'a> {
+    fn from(err: CycleError<&'a str>) -> Self {
+        SolveError::Cycle(err.key)
+    }
+}
+
+// the value of `humn` that makes both sides of `root` equal.
+fn solve_humn<'a>(map: &InsMap<'a>) -> Result<i64, SolveError<'a>> {
+    let mut uses_humn = Memo::new(|uses_humn, field: &'a str| match map[field] {
+        _ if field == HUMN => Ok(true),
+        Value::Lit(_) => Ok(false),
+        Value::Expr((fst, _, snd)) => Ok(uses_humn(fst)? | uses_humn(snd)?),
+    });
+    let Value::Expr((fst, _, snd)) = map["root"] else {
+        return Err(SolveError::NoUnknown);
+    };
+
+    // the side with `humn` must equal the other one.
+    let (mut field, mut target) = match (uses_humn.get(fst)?, uses_humn.get(snd)?) {
+        (true, true) => return Err(SolveError::BothSides),
+        (false, false) => return Err(SolveError::NoUnknown),
+        (true, false) => (fst, exact_field(snd, map)?),
+        (false, true) => (snd, exact_field(fst, map)?),
     };
 
-    if *map[field].borrow() != Value::Lit(result) {
-        *map[field].borrow_mut() = Value::Lit(result);
+    // then undo operations one by one, down to `humn`.
+    while field != HUMN {
+        let Value::Expr((fst, op, snd)) = map[field] else {
+            unreachable!("only {} can be a literal using {}", HUMN, HUMN)
+        };
+        (field, target) = match (uses_humn.get(fst)?, uses_humn.get(snd)?) {
+            (true, true) => return Err(SolveError::NonLinear(field)),
+            (true, false) => (fst, invert_fst(field, target, op, exact_field(snd, map)?)?),
+            (false, true) => (snd, invert_snd(field, exact_field(fst, map)?, op, target)?),
+            (false, false) => unreachable!("{} uses {}", field, HUMN),
+        };
     }
 
-    result
+    target.to_integer().ok_or(SolveError::NotInteger(target))
+}
+
+// the `x` for which `x op snd == target`, at the monkey `field`.
+fn invert_fst<'a>(
+    field: &'a str,
+    target: Ratio,
+    op: Op,
+    snd: Ratio,
+) -> Result<Ratio, SolveError<'a>> {
+    let value = match op {
+        Op::Add => target.apply(Op::Sub, snd),
+        Op::Sub => target.apply(Op::Add, snd),
+        Op::Mul => target.apply(Op::Div, snd),
+        Op::Div => {
+            // nothing divided by zero gives a number.
+            if snd == Ratio::from(0) {
+                return Err(SolveError::NoSolution(field));
+            }
+            target.apply(Op::Mul, snd)
+        }
+    };
+    value.ok_or(SolveError::NoSolution(field))
+}
+
+// the `x` for which `fst op x == target`, at the monkey `field`.
+fn invert_snd<'a>(
+    field: &'a str,
+    fst: Ratio,
+    op: Op,
+    target: Ratio,
+) -> Result<Ratio, SolveError<'a>> {
+    let value = match op {
+        Op::Add => target.apply(Op::Sub, fst),
+        Op::Sub => fst.apply(Op::Sub, target),
+        Op::Mul => target.apply(Op::Div, fst),
+        Op::Div => fst.apply(Op::Div, target),
+    };
+    value.ok_or(SolveError::NoSolution(field))
+}
+
+fn exact_field<'a>(field: &'a str, map: &InsMap<'a>) -> Result<Ratio, SolveError<'a>> {
+    match map[field] {
+        Value::Lit(v) => Ok(Ratio::from(v)),
+        Value::Expr((fst, op, snd)) => exact_field(fst, map)?
+            .apply(op, exact_field(snd, map)?)
+            .ok_or(SolveError::NoSolution(field)),
+    }
+}
+
+/// An exact fraction, kept reduced with a positive denominator.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    fn new(num: i128, den: i128) -> Self {
+        let divisor = gcd(num, den) * den.signum();
+        Ratio {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    /// `None` when dividing by zero.
+    fn apply(self, op: Op, other: Ratio) -> Option<Ratio> {
+        let (a, b) = (self, other);
+        match op {
+            Op::Add => Some(Ratio::new(a.num * b.den + b.num * a.den, a.den * b.den)),
+            Op::Sub => Some(Ratio::new(a.num * b.den - b.num * a.den, a.den * b.den)),
+            Op::Mul => Some(Ratio::new(a.num * b.num, a.den * b.den)),
+            Op::Div => (b.num != 0).then(|| Ratio::new(a.num * b.den, a.den * b.num)),
+        }
+    }
+
+    fn to_integer(self) -> Option<i64> {
+        (self.den == 1).then(|| i64::try_from(self.num).ok())?
+    }
+}
+
+impl From<i64> for Ratio {
+    fn from(value: i64) -> Self {
+        Ratio {
+            num: value as i128,
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 21);
+    let input = &advent_of_code::read_input(21);
     advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, "src/inputs/21.txt");
+    advent_of_code::solve!(2, part_two, input);
 }
 
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -76,23 +246,19 @@ struct Ins<'a> {
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alphanumeric1, digit1},
-    combinator::{all_consuming, map, opt},
-    multi::separated_list1,
-    sequence::{separated_pair, terminated, tuple},
+    character::complete::alphanumeric1,
+    combinator::map,
+    sequence::{separated_pair, tuple},
     IResult,
 };
 
-fn input_parser(i: &str) -> IResult<&str, Vec<Ins>> {
-    all_consuming(terminated(
-        separated_list1(tag("\n"), ins_parser),
-        opt(tag("\n")),
-    ))(i)
+fn input_parser(i: &str) -> IResult<&str, Vec<Ins<'_>>> {
+    lines(ins_parser)(i)
 }
 
-fn value_parser(i: &str) -> IResult<&str, Value> {
+fn value_parser(i: &str) -> IResult<&str, Value<'_>> {
     alt((
-        map(digit1, |v: &str| Value::Lit(v.parse::<i64>().unwrap())),
+        map(int, Value::Lit),
         map(
             tuple((
                 alphanumeric1,
@@ -109,7 +275,7 @@ fn value_parser(i: &str) -> IResult<&str, Value> {
     ))(i)
 }
 
-fn ins_parser(i: &str) -> IResult<&str, Ins> {
+fn ins_parser(i: &str) -> IResult<&str, Ins<'_>> {
     map(
         separated_pair(alphanumeric1, tag(": "), value_parser),
         |(res, val)| Ins { res, val },
@@ -128,6 +294,47 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two("src/examples/21.txt"), Some(301));
+        let input = advent_of_code::read_file("examples", 21);
+        assert_eq!(part_two(&input), Some(301));
+    }
+
+    fn solve(input: &str) -> Result<i64, SolveError<'_>> {
+        let ins = parse_all(input_parser, input).unwrap();
+        let map: InsMap = ins.iter().map(|i| (i.res, i.val)).collect();
+        solve_humn(&map)
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        assert_eq!(part_two("root: humn + humn\nhumn: 5"), None);
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert_eq!(
+            solve("root: humn + twice\ntwice: humn * two\ntwo: 2\nhumn: 5"),
+            Err(SolveError::BothSides)
+        );
+        assert_eq!(
+            solve("root: sqrt + four\nsqrt: humn * humn\nfour: 4\nhumn: 1"),
+            Err(SolveError::NonLinear("sqrt"))
+        );
+        assert_eq!(
+            solve("root: zero + four\nzero: humn * nope\nnope: 0\nfour: 4\nhumn: 1"),
+            Err(SolveError::NoSolution("zero"))
+        );
+        assert_eq!(
+            solve("root: part + four\npart: humn / zero\nzero: 0\nfour: 4\nhumn: 1"),
+            Err(SolveError::NoSolution("part"))
+        );
+        assert_eq!(
+            solve("root: triple + four\ntriple: humn * three\nthree: 3\nfour: 4\nhumn: 1"),
+            Err(SolveError::NotInteger(Ratio::new(4, 3)))
+        );
+        // the unknown can be a divisor, or on the right of a subtraction.
+        assert_eq!(
+            solve("root: four + part\npart: twenty / diff\ndiff: ten - humn\nten: 10\ntwenty: 20\nfour: 4\nhumn: 1"),
+            Ok(5)
+        );
     }
 }
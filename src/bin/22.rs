@@ -1,19 +1,23 @@
+use advent_of_code::helpers::num::isqrt;
+use advent_of_code::helpers::parse::{grid, parse_all, uint};
+use advent_of_code::helpers::Dir4::{self, *};
+use advent_of_code::helpers::{Grid, Point2, Point3};
 use rustc_hash::FxHashMap;
 
 pub fn part_one(input: &str) -> Option<i32> {
-    let (_, (board, instructions)) = input_parser(input).unwrap();
+    let (board, instructions) = parse_all(input_parser, input).unwrap();
     let mut player = Player {
         pos: board.start,
-        dir: Dir::Right,
+        dir: Right,
     };
     for ins in instructions {
         player = match ins {
             Ins::TurnClockwise => Player {
-                dir: turn_clockwise(player.dir),
+                dir: player.dir.turn_right(),
                 pos: player.pos,
             },
             Ins::TurnCounterClockwise => Player {
-                dir: turn_counter_clockwise(player.dir),
+                dir: player.dir.turn_left(),
                 pos: player.pos,
             },
             Ins::Forward(v) => move_player(v, &player, &board),
@@ -23,22 +27,23 @@ pub fn part_one(input: &str) -> Option<i32> {
 }
 
 pub fn part_two(input: &str) -> Option<i32> {
-    let (_, (board, instructions)) = input_parser(input).unwrap();
+    let (board, instructions) = parse_all(input_parser, input).unwrap();
+    let cube = Cube::fold(&board)?;
     let mut player = Player {
         pos: board.start,
-        dir: Dir::Right,
+        dir: Right,
     };
     for ins in instructions {
         player = match ins {
             Ins::TurnClockwise => Player {
-                dir: turn_clockwise(player.dir),
+                dir: player.dir.turn_right(),
                 pos: player.pos,
             },
             Ins::TurnCounterClockwise => Player {
-                dir: turn_counter_clockwise(player.dir),
+                dir: player.dir.turn_left(),
                 pos: player.pos,
             },
-            Ins::Forward(v) => move_player_cube(v, &player, &board),
+            Ins::Forward(v) => move_player_cube(v, &player, &board, &cube),
         }
     }
     Some(player_score(&player))
@@ -56,57 +61,32 @@ fn player_score(player: &Player) -> i32 {
     row + col + dir
 }
 
-fn step_for_dir(dir: &Dir) -> Pos {
-    let step = match dir {
-        Left => (-1, 0),
-        Up => (0, -1),
-        Right => (1, 0),
-        Down => (0, 1),
-    };
-    Pos {
-        x: step.0,
-        y: step.1,
-    }
-}
-
 fn move_player(steps: u32, player: &Player, board: &Board) -> Player {
-    let (max_x, max_y) = board.bounds;
+    let (max_x, max_y) = board.bounds();
 
-    let step = step_for_dir(&player.dir);
+    let step = player.dir.step();
     let mut current = player.pos;
 
     for _ in 0..steps {
         let next = current + step;
 
-        match board.cells.get(&next) {
+        match board.cell(next) {
             Some(Cell::Tile) => {
                 current = next;
             }
             Some(Cell::Wall) => break,
             None => {
                 let mut other_point = match player.dir {
-                    Left => Pos {
-                        x: max_x,
-                        y: player.pos.y,
-                    },
-                    Right => Pos {
-                        x: 0,
-                        y: player.pos.y,
-                    },
-                    Up => Pos {
-                        x: player.pos.x,
-                        y: max_y,
-                    },
-                    Down => Pos {
-                        x: player.pos.x,
-                        y: 0,
-                    },
+                    Left => Pos::new(max_x, player.pos.y),
+                    Right => Pos::new(0, player.pos.y),
+                    Up => Pos::new(player.pos.x, max_y),
+                    Down => Pos::new(player.pos.x, 0),
                 };
 
-                while board.cells.get(&other_point).is_none() {
+                while board.cell(other_point).is_none() {
                     other_point += step;
                 }
-                if let Some(Cell::Tile) = board.cells.get(&other_point) {
+                if let Some(Cell::Tile) = board.cell(other_point) {
                     current = other_point;
                 } else {
                     break;
@@ -121,27 +101,25 @@ fn move_player(steps: u32, player: &Player, board: &Board) -> Player {
     }
 }
 
-fn move_player_cube(steps: u32, player: &Player, board: &Board) -> Player {
+fn move_player_cube(steps: u32, player: &Player, board: &Board, cube: &Cube) -> Player {
     let mut dir = player.dir;
     let mut pos = player.pos;
 
     for _ in 0..steps {
-        let step = step_for_dir(&dir);
+        let step = dir.step();
         let next = pos + step;
 
-        match board.cells.get(&next) {
+        match board.cell(next) {
             Some(Cell::Tile) => {
                 pos = next;
             }
             Some(Cell::Wall) => break,
             None => {
-                let (new_dir, other_point) = wrap_around(&dir, &pos);
+                let (new_dir, other_point) = cube.wrap_around(dir, pos);
 
-                if let Some(Cell::Tile) = board.cells.get(&other_point) {
+                if let Some(Cell::Tile) = board.cell(other_point) {
                     pos = other_point;
                     dir = new_dir;
-                } else if board.cells.get(&other_point).is_none() {
-                    panic!("this is wrong: {:?} -> {:?}", pos, other_point)
                 } else {
                     break;
                 }
@@ -152,46 +130,156 @@ fn move_player_cube(steps: u32, player: &Player, board: &Board) -> Player {
     Player { pos, dir }
 }
 
-fn wrap_around(dir: &Dir, pos: &Pos) -> (Dir, Pos) {
-    let (new_dir, x, y) = match dir {
-        Left => match pos.y {
-            0..=49 => (Right, 0, (49 - pos.y) + 100),
-            50..=99 => (Down, pos.y - 50, 100),
-            100..=149 => (Right, 50, 49 - (pos.y - 100)),
-            150..=199 => (Down, (pos.y - 150) + 50, 0),
-            _ => panic!("oopsies"),
-        },
-        Right => match pos.y {
-            0..=49 => (Left, 99, 149 - pos.y),
-            50..=99 => (Up, 100 + (pos.y - 50), 49),
-            100..=149 => (Left, 149, 149 - pos.y),
-            150..=199 => (Up, (pos.y - 150) + 50, 149),
-            _ => panic!("oopsies"),
-        },
-        Up => match pos.x {
-            0..=49 => (Right, 50, pos.x + 50),
-            50..=99 => (Right, 0, (pos.x - 50) + 150),
-            100..=149 => (Up, pos.x - 100, 199),
-            _ => panic!("oopsies"),
-        },
-        Down => match pos.x {
-            0..=49 => (Down, 100 + pos.x, 0),
-            50..=99 => (Left, 49, (pos.x - 50) + 150),
-            100..=149 => (Left, 99, 50 + (pos.x - 100)),
-            _ => panic!("oopsies"),
-        },
-    };
-    (new_dir, Pos { x, y })
+type Vec3 = Point3<i32>;
+
+// a face of the net, along with where its normal and its right and down directions point once
+// folded into a cube.
+#[derive(PartialEq, Debug)]
+struct Face {
+    origin: Pos,
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    // the direction of the edge on the `dir` side, seen from the center of the cube.
+    fn edge(&self, dir: Dir4) -> Vec3 {
+        match dir {
+            Up => -self.down,
+            Right => self.right,
+            Down => self.down,
+            Left => -self.right,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Cube {
+    size: i32,
+    faces: Vec<Face>,
+    // leaving a face through an edge leads to the edge of another face.
+    edges: FxHashMap<(usize, Dir4), (usize, Dir4)>,
+}
+
+impl Cube {
+    /// Folds the board, `None` when it is not one of the eleven nets of a cube.
+    fn fold(board: &Board) -> Option<Self> {
+        let area = board.cells.iter().filter(|(_, c)| c.is_some()).count() as i32;
+        let size = isqrt(area / 6);
+        if size == 0 || size * size * 6 != area {
+            return None;
+        }
+
+        let (max_x, max_y) = board.bounds();
+        let mut origins = vec![];
+        for y in (0..=max_y).step_by(size as usize) {
+            for x in (0..=max_x).step_by(size as usize) {
+                if board.cell(Pos::new(x, y)).is_some() {
+                    origins.push(Pos::new(x, y));
+                }
+            }
+        }
+        if origins.len() != 6 {
+            return None;
+        }
+
+        // roll the cube from the first face over to its neighbours in the net.
+        let mut faces = vec![Face {
+            origin: origins[0],
+            normal: Vec3::new(0, 0, 1),
+            right: Vec3::new(1, 0, 0),
+            down: Vec3::new(0, 1, 0),
+        }];
+        let mut to_visit = vec![0];
+        while let Some(current) = to_visit.pop() {
+            for dir in Dir4::iter() {
+                let origin = faces[current].origin + dir.step() * size;
+                if !origins.contains(&origin) || faces.iter().any(|f| f.origin == origin) {
+                    continue;
+                }
+                let Face {
+                    normal,
+                    right,
+                    down,
+                    ..
+                } = faces[current];
+                let (normal, right, down) = match dir {
+                    Up => (-down, right, normal),
+                    Right => (right, -normal, down),
+                    Down => (down, right, -normal),
+                    Left => (-right, normal, down),
+                };
+                faces.push(Face {
+                    origin,
+                    normal,
+                    right,
+                    down,
+                });
+                to_visit.push(faces.len() - 1);
+            }
+        }
+
+        let mut edges = FxHashMap::default();
+        for (from, face) in faces.iter().enumerate() {
+            for dir in Dir4::iter() {
+                let to = faces.iter().position(|f| f.normal == face.edge(dir))?;
+                let entry = Dir4::iter().find(|d| faces[to].edge(*d) == face.normal)?;
+                edges.insert((from, dir), (to, entry));
+            }
+        }
+
+        Some(Cube { size, faces, edges })
+    }
+
+    /// Where and in which direction we end up when walking off the face at `pos` towards `dir`.
+    fn wrap_around(&self, dir: Dir4, pos: Pos) -> (Dir4, Pos) {
+        let from = self
+            .faces
+            .iter()
+            .position(|f| {
+                let local = pos - f.origin;
+                (0..self.size).contains(&local.x) && (0..self.size).contains(&local.y)
+            })
+            .expect("not on the cube");
+        let (to, entry) = self.edges[&(from, dir)];
+
+        // both faces go clockwise around the shared edge, in opposite directions.
+        let offset = self.size - 1 - self.edge_offset(dir, pos - self.faces[from].origin);
+        let local = self.edge_point(entry, offset);
+        (entry.turn_around(), self.faces[to].origin + local)
+    }
+
+    // how far along the edge on the `dir` side the point is, going clockwise around its face.
+    fn edge_offset(&self, dir: Dir4, local: Pos) -> i32 {
+        let last = self.size - 1;
+        match dir {
+            Up => local.x,
+            Right => local.y,
+            Down => last - local.x,
+            Left => last - local.y,
+        }
+    }
+
+    fn edge_point(&self, dir: Dir4, offset: i32) -> Pos {
+        let last = self.size - 1;
+        match dir {
+            Up => Pos::new(offset, 0),
+            Right => Pos::new(last, offset),
+            Down => Pos::new(last - offset, last),
+            Left => Pos::new(0, last - offset),
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
 struct Player {
     pos: Pos,
-    dir: Dir,
+    dir: Dir4,
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 22);
+    let input = &advent_of_code::read_input(22);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -202,47 +290,32 @@ enum Cell {
     Wall,
 }
 
-#[derive(PartialEq, Debug, Eq, Hash, Clone, Copy)]
-struct Pos {
-    x: i32,
-    y: i32,
-}
-
-impl std::ops::Add for Pos {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-impl std::ops::AddAssign for Pos {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        };
-    }
-}
+type Pos = Point2<i32>;
 
 #[derive(PartialEq, Debug)]
 struct Board {
-    cells: FxHashMap<Pos, Cell>,
+    cells: Grid<Option<Cell>>,
     start: Pos,
-    bounds: (i32, i32),
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-enum Dir {
-    Left,
-    Up,
-    Right,
-    Down,
+impl Board {
+    // `None` outside of the map, including the blank space around it.
+    fn cell(&self, pos: Pos) -> Option<&Cell> {
+        if pos.x < 0 || pos.y < 0 {
+            return None;
+        }
+        self.cells
+            .get((pos.x as usize, pos.y as usize))
+            .and_then(|c| c.as_ref())
+    }
+
+    fn bounds(&self) -> (i32, i32) {
+        (
+            self.cells.width() as i32 - 1,
+            self.cells.height() as i32 - 1,
+        )
+    }
 }
-use crate::Dir::*;
 
 #[derive(PartialEq, Debug)]
 enum Ins {
@@ -251,78 +324,28 @@ enum Ins {
     TurnCounterClockwise,
 }
 
-fn turn_clockwise(dir: Dir) -> Dir {
-    match dir {
-        Left => Up,
-        Up => Right,
-        Right => Down,
-        Down => Left,
-    }
-}
-fn turn_counter_clockwise(dir: Dir) -> Dir {
-    match dir {
-        Left => Down,
-        Up => Left,
-        Right => Up,
-        Down => Right,
-    }
-}
-
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{all_consuming, map, opt},
-    multi::{many1, separated_list1},
-    sequence::{separated_pair, terminated},
+    branch::alt, bytes::complete::tag, combinator::map, multi::many1, sequence::separated_pair,
     IResult,
 };
 
 fn input_parser(i: &str) -> IResult<&str, (Board, Vec<Ins>)> {
-    all_consuming(terminated(
-        separated_pair(board_parser, tag("\n\n"), ins_parser),
-        opt(tag("\n")),
-    ))(i)
+    separated_pair(board_parser, tag("\n\n"), ins_parser)(i)
 }
 
 fn board_parser(i: &str) -> IResult<&str, Board> {
     map(
-        separated_list1(tag("\n"), many1(alt((tag(" "), tag("."), tag("#"))))),
-        |rows: Vec<Vec<&str>>| {
-            let mut result = FxHashMap::default();
-            let mut first = None;
-            let mut max_x = 0;
-            let mut max_y = 0;
-
-            for (y, row) in rows.iter().enumerate() {
-                for (x, c) in row.iter().enumerate() {
-                    let pos = Pos {
-                        x: x as i32,
-                        y: y as i32,
-                    };
-
-                    max_x = max_x.max(x as i32);
-                    max_y = max_y.max(y as i32);
-
-                    match *c {
-                        "." => {
-                            if first.is_none() {
-                                first = Some(pos);
-                            }
-                            result.insert(pos, Cell::Tile);
-                        }
-                        "#" => {
-                            result.insert(pos, Cell::Wall);
-                        }
-                        _ => (),
-                    }
-                }
-            }
+        grid(" .#", |c| match c {
+            '.' => Some(Cell::Tile),
+            '#' => Some(Cell::Wall),
+            _ => None,
+        }),
+        |cells: Grid<Option<Cell>>| {
+            let (x, y) = cells.find(|c| *c == Some(Cell::Tile)).unwrap();
 
             Board {
-                cells: result,
-                start: first.unwrap(),
-                bounds: (max_x, max_y),
+                cells,
+                start: Pos::new(x as i32, y as i32),
             }
         },
     )(i)
@@ -332,24 +355,19 @@ fn ins_parser(i: &str) -> IResult<&str, Vec<Ins>> {
     many1(alt((
         map(tag("R"), |_| Ins::TurnClockwise),
         map(tag("L"), |_| Ins::TurnCounterClockwise),
-        map(digit1, |v: &str| Ins::Forward(v.parse().unwrap())),
+        map(uint, Ins::Forward),
     )))(i)
 }
 
 fn _print_board(board: &Board, player: &Player) {
-    let (max_x, max_y) = board.bounds;
+    let (max_x, max_y) = board.bounds();
 
     for y in 0..=max_y {
         for x in 0..=max_x {
-            if player.pos == (Pos { x, y }) {
-                match player.dir {
-                    Dir::Right => print!(">"),
-                    Dir::Up => print!("^"),
-                    Dir::Left => print!("<"),
-                    Dir::Down => print!("v"),
-                }
+            if player.pos == Pos::new(x, y) {
+                print!("{}", player.dir)
             } else {
-                match board.cells.get(&Pos { x, y }) {
+                match board.cell(Pos::new(x, y)) {
                     Some(Cell::Tile) => print!("."),
                     Some(Cell::Wall) => print!("#"),
                     None => print!(" "),
@@ -367,7 +385,7 @@ mod tests {
     #[test]
     fn test_parse() {
         let input = advent_of_code::read_file("examples", 22);
-        assert!(input_parser(&input).is_ok());
+        assert!(parse_all(input_parser, &input).is_ok());
     }
 
     #[test]
@@ -375,4 +393,58 @@ mod tests {
         let input = advent_of_code::read_file("examples", 22);
         assert_eq!(part_one(&input), Some(6032));
     }
+
+    #[test]
+    fn test_fold() {
+        let input = advent_of_code::read_file("examples", 22);
+        let (board, _) = parse_all(input_parser, &input).unwrap();
+        let cube = Cube::fold(&board).unwrap();
+        assert_eq!(cube.size, 4);
+        assert_eq!(cube.faces[0].origin, Pos::new(8, 0));
+        // walking up from the top face, we arrive going down on the left of the middle row.
+        assert_eq!(cube.wrap_around(Up, Pos::new(9, 0)), (Down, Pos::new(2, 4)));
+        // the example from the puzzle, going right from A to B.
+        assert_eq!(
+            cube.wrap_around(Right, Pos::new(11, 5)),
+            (Down, Pos::new(14, 8))
+        );
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_fold_property() {
+        advent_of_code::generators::check_property(22, 20, |input| {
+            let (board, _) = parse_all(input_parser, input).unwrap();
+            let cube = Cube::fold(&board).unwrap();
+            let mut normals: Vec<_> = cube.faces.iter().map(|f| f.normal).collect();
+            normals.sort();
+            normals.dedup();
+            assert_eq!(normals.len(), 6);
+
+            // walking off any edge and straight back leads to where we started.
+            for ((from, dir), (to, entry)) in &cube.edges {
+                assert_eq!(cube.edges[&(*to, *entry)], (*from, *dir));
+                for offset in 0..cube.size {
+                    let pos = cube.faces[*from].origin + cube.edge_point(*dir, offset);
+                    let (back_dir, other) = cube.wrap_around(*dir, pos);
+                    assert!(board.cell(other).is_some());
+                    assert_eq!(
+                        cube.wrap_around(back_dir.turn_around(), other),
+                        (dir.turn_around(), pos)
+                    );
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 22);
+        assert_eq!(part_two(&input), Some(5031));
+    }
+
+    #[test]
+    fn test_not_a_cube() {
+        assert_eq!(part_two("....\n....\n\n3R2\n"), None);
+    }
 }
@@ -1,15 +1,19 @@
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::collections::VecDeque;
+use advent_of_code::helpers::num::lcm;
+use advent_of_code::helpers::parse::{grid, parse_all};
+use advent_of_code::helpers::search::bfs_cost;
+use advent_of_code::helpers::sparse_grid::{Bounds, SparseGrid};
+use advent_of_code::helpers::{Dir4, Grid, Point2};
+use rustc_hash::FxHashMap;
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (_, board) = input_parser(input).unwrap();
+    let board = parse_all(board_parser, input).unwrap();
     let mut cache = FxHashMap::from_iter([(0, board.clone())]);
 
     run_bfs(board.start, board.end, 0, &mut cache)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (_, board) = input_parser(input).unwrap();
+    let board = parse_all(board_parser, input).unwrap();
     let mut cache = FxHashMap::from_iter([(0, board.clone())]);
 
     let there = run_bfs(board.start, board.end, 0, &mut cache)?;
@@ -18,7 +22,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 24);
+    let input = &advent_of_code::read_input(24);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -30,108 +34,76 @@ fn run_bfs(
     initial_turn: usize,
     cache: &mut FxHashMap<usize, Board>,
 ) -> Option<usize> {
-    let mut to_visit = VecDeque::new();
-    let mut visited: FxHashSet<(Pos, usize)> = FxHashSet::default();
-
-    to_visit.push_back((start, initial_turn));
-
-    while let Some((node, cost)) = to_visit.pop_front() {
-        if node == end {
-            return Some(cost);
-        }
-
-        let next_cost = cost + 1;
-        ensure_in_cache(cache, next_cost);
-
-        for next in possible_moves(&node, &cache[&next_cost]) {
-            if visited.contains(&(next, next_cost)) {
-                continue;
-            }
-            visited.insert((next, next_cost));
-            to_visit.push_back((next, next_cost));
-        }
-    }
-
-    None
+    // nodes are positions at a given turn, as blizzards move every turn.
+    // blizzards are back where they started after `period` turns, so only those are cached.
+    let period = cache[&0].period();
+    bfs_cost(
+        [(start, initial_turn)],
+        |&(node, turn)| {
+            let next_turn = turn + 1;
+            ensure_in_cache(cache, next_turn % period, period);
+            possible_moves(&node, &cache[&(next_turn % period)])
+                .into_iter()
+                .map(move |next| (next, next_turn))
+        },
+        |(node, _)| *node == end,
+    )
+    .map(|cost| initial_turn + cost)
 }
 
-fn ensure_in_cache(cache: &mut FxHashMap<usize, Board>, turn: usize) {
+fn ensure_in_cache(cache: &mut FxHashMap<usize, Board>, turn: usize, period: usize) {
     if cache.get(&turn).is_none() {
-        let old_board = &cache[&(turn - 1)];
+        let old_board = &cache[&((turn + period - 1) % period)];
         let new_board = old_board.evolve();
         cache.insert(turn, new_board);
     }
 }
 
 fn possible_moves(pos: &Pos, board: &Board) -> Vec<Pos> {
-    let (_, max_y) = board.bounds;
-
-    pos.successors()
+    successors(pos)
         .iter()
         .copied()
-        .filter(|p| {
-            p.y >= 0 && p.y <= max_y && !board.walls.contains(p) && !board.blizzards.contains_key(p)
-        })
+        .filter(|p| !board.is_wall(p) && !board.blizzards.contains(p))
         .collect()
 }
 
 // data structures
-#[derive(PartialEq, Debug, Clone, Copy)]
-enum Blizzard {
-    Upper,
-    Downer,
-    Lefter,
-    Righter,
-}
-
-impl Blizzard {
-    fn step(&self) -> Pos {
-        let (x, y) = match self {
-            Upper => (0, -1),
-            Downer => (0, 1),
-            Lefter => (-1, 0),
-            Righter => (1, 0),
-        };
-
-        Pos { x, y }
-    }
-}
-
-use std::fmt;
-
-impl fmt::Display for Blizzard {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Upper => "^",
-                Downer => "v",
-                Lefter => "<",
-                Righter => ">",
-            }
-        )
-    }
-}
-
-use crate::Blizzard::*;
+// a blizzard is the direction it blows to, printed as its arrow.
+type Blizzard = Dir4;
 
 #[derive(PartialEq, Debug, Clone)]
 struct Board {
     start: Pos,
     end: Pos,
     bounds: (i32, i32),
-    walls: FxHashSet<Pos>,
-    blizzards: FxHashMap<Pos, Vec<Blizzard>>,
+    walls: Grid<bool>,
+    blizzards: SparseGrid<Vec<Blizzard>, Pos>,
 }
 
 impl Board {
+    // anything outside of the valley counts as a wall.
+    fn is_wall(&self, pos: &Pos) -> bool {
+        pos.x < 0
+            || pos.y < 0
+            || self
+                .walls
+                .get((pos.x as usize, pos.y as usize))
+                .copied()
+                .unwrap_or(true)
+    }
+
+    // the turns it takes every blizzard to cross the valley both ways and come back.
+    fn period(&self) -> usize {
+        let (max_x, max_y) = self.bounds;
+        lcm(max_x - 1, max_y - 1) as usize
+    }
+
     fn evolve(&self) -> Self {
-        let mut new_blizzs: FxHashMap<Pos, Vec<Blizzard>> = FxHashMap::default();
+        let mut new_blizzs = SparseGrid::new();
 
-        for (pos, blizzs) in &self.blizzards {
+        for (pos, blizzs) in self.blizzards.iter() {
             for blizz in blizzs {
-                let new_blizz_pos = pos.add_and_wrap(blizz.step(), self.bounds);
+                let new_blizz_pos = add_and_wrap(pos, blizz.step(), self.bounds);
                 add_blizzard(&new_blizz_pos, *blizz, &mut new_blizzs);
             }
         }
@@ -147,107 +119,63 @@ impl Board {
 
     fn _print(&self, player: &Pos) {
         let (max_x, max_y) = self.bounds;
+        let area = Bounds {
+            min_x: 0,
+            max_x,
+            min_y: 0,
+            max_y,
+        };
 
-        for y in 0..=max_y {
-            for x in 0..=max_x {
-                let pos = Pos { x, y };
-                if *player == pos {
-                    print!("E");
-                } else if self.walls.contains(&pos) {
-                    print!("#");
-                } else if let Some(v) = self.blizzards.get(&pos) {
-                    if v.len() > 1 {
-                        print!("{}", v.len());
-                    } else {
-                        print!("{}", v[0]);
-                    }
-                } else {
-                    print!(".");
+        let result = self.blizzards.render_in(area, |pos, blizzs| {
+            if *player == pos {
+                "E".to_string()
+            } else if self.is_wall(&pos) {
+                "#".to_string()
+            } else {
+                match blizzs {
+                    Some(v) if v.len() > 1 => v.len().to_string(),
+                    Some(v) => v[0].to_string(),
+                    None => ".".to_string(),
                 }
             }
-            println!()
-        }
-        println!()
+        });
+        println!("{}\n", result);
     }
 }
 
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    combinator::{all_consuming, map, opt},
-    multi::{many1, separated_list1},
-    sequence::terminated,
-    IResult,
-};
-
-fn input_parser(i: &str) -> IResult<&str, Board> {
-    all_consuming(terminated(board_parser, opt(tag("\n"))))(i)
-}
+use nom::{combinator::map, IResult};
 
 fn board_parser(i: &str) -> IResult<&str, Board> {
-    map(
-        separated_list1(
-            tag("\n"),
-            many1(alt((
-                tag("."),
-                tag(">"),
-                tag("<"),
-                tag("^"),
-                tag("v"),
-                tag("#"),
-            ))),
-        ),
-        |rows: Vec<Vec<&str>>| {
-            let mut walls = FxHashSet::default();
-            let mut blizzards = FxHashMap::default();
-
-            for (y, row) in rows.iter().enumerate() {
-                for (x, c) in row.iter().enumerate() {
-                    let pos = Pos {
-                        x: x as i32,
-                        y: y as i32,
-                    };
-
-                    match *c {
-                        "#" => {
-                            walls.insert(pos);
-                        }
-                        ">" => {
-                            add_blizzard(&pos, Righter, &mut blizzards);
-                        }
-                        "<" => {
-                            add_blizzard(&pos, Lefter, &mut blizzards);
-                        }
-                        "^" => {
-                            add_blizzard(&pos, Upper, &mut blizzards);
-                        }
-                        "v" => {
-                            add_blizzard(&pos, Downer, &mut blizzards);
-                        }
-                        "." => (),
-                        _ => panic!("did not expect this"),
-                    }
+    map(grid(".<>^v#", |c| c), |grid: Grid<char>| {
+        let mut blizzards = SparseGrid::new();
+
+        for ((x, y), c) in grid.iter() {
+            let pos = Pos::new(x as i32, y as i32);
+
+            match c {
+                '>' | '<' | '^' | 'v' => {
+                    add_blizzard(&pos, Dir4::from_char(*c).unwrap(), &mut blizzards)
                 }
+                '#' | '.' => (),
+                _ => panic!("did not expect this"),
             }
+        }
 
-            let max_x = rows[0].len() as i32 - 1;
-            let max_y = rows.len() as i32 - 1;
-
-            Board {
-                start: Pos { x: 1, y: 0 },
-                end: Pos {
-                    x: max_x - 1,
-                    y: max_y,
-                },
-                bounds: (max_x, max_y),
-                walls,
-                blizzards,
-            }
-        },
-    )(i)
+        let max_x = grid.width() as i32 - 1;
+        let max_y = grid.height() as i32 - 1;
+        let walls = grid.map(|c| *c == '#');
+
+        Board {
+            start: Pos::new(1, 0),
+            end: Pos::new(max_x - 1, max_y),
+            bounds: (max_x, max_y),
+            walls,
+            blizzards,
+        }
+    })(i)
 }
 
-fn add_blizzard(pos: &Pos, blizzard: Blizzard, blizzards: &mut FxHashMap<Pos, Vec<Blizzard>>) {
+fn add_blizzard(pos: &Pos, blizzard: Blizzard, blizzards: &mut SparseGrid<Vec<Blizzard>, Pos>) {
     if let Some(old) = blizzards.get_mut(pos) {
         old.push(blizzard);
     } else {
@@ -255,65 +183,39 @@ fn add_blizzard(pos: &Pos, blizzard: Blizzard, blizzards: &mut FxHashMap<Pos, Ve
     }
 }
 
-#[derive(PartialEq, Debug, Eq, Hash, Clone, Copy)]
-struct Pos {
-    x: i32,
-    y: i32,
-}
+type Pos = Point2<i32>;
 
-impl Pos {
-    fn add_and_wrap(&self, other: Self, bounds: (i32, i32)) -> Self {
-        let (max_x, max_y) = bounds;
-        let mut new = *self + other;
+fn add_and_wrap(pos: &Pos, other: Pos, bounds: (i32, i32)) -> Pos {
+    let (max_x, max_y) = bounds;
+    let mut new = *pos + other;
 
-        if new.x < 1 {
-            new.x = max_x - 1;
-        }
-        if new.x > max_x - 1 {
-            new.x = 1;
-        }
-        if new.y < 1 {
-            new.y = max_y - 1;
-        }
-        if new.y > max_y - 1 {
-            new.y = 1;
-        }
-
-        new
+    if new.x < 1 {
+        new.x = max_x - 1;
     }
-
-    fn successors(&self) -> Vec<Pos> {
-        vec![
-            Pos { x: 0, y: -1 }, // up
-            Pos { x: 1, y: 0 },  // right
-            Pos { x: 0, y: 1 },  // down
-            Pos { x: -1, y: 0 }, // left
-            Pos { x: 0, y: 0 },  // wait
-        ]
-        .iter()
-        .map(|step| *self + *step)
-        .collect()
+    if new.x > max_x - 1 {
+        new.x = 1;
     }
-}
-
-impl std::ops::Add for Pos {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    if new.y < 1 {
+        new.y = max_y - 1;
+    }
+    if new.y > max_y - 1 {
+        new.y = 1;
     }
+
+    new
 }
 
-impl std::ops::AddAssign for Pos {
-    fn add_assign(&mut self, other: Self) {
-        *self = Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        };
-    }
+fn successors(pos: &Pos) -> Vec<Pos> {
+    [
+        Pos::new(0, -1), // up
+        Pos::new(1, 0),  // right
+        Pos::new(0, 1),  // down
+        Pos::new(-1, 0), // left
+        Pos::new(0, 0),  // wait
+    ]
+    .iter()
+    .map(|step| *pos + *step)
+    .collect()
 }
 
 #[cfg(test)]
@@ -323,7 +225,7 @@ mod tests {
     #[test]
     fn test_parse() {
         let input = advent_of_code::read_file("examples", 24);
-        assert!(input_parser(&input).is_ok());
+        assert!(parse_all(board_parser, &input).is_ok());
     }
 
     #[test]
@@ -1,168 +1,20 @@
-use itertools::EitherOrBoth::{Both, Left, Right};
-use itertools::Itertools;
-use parse_display::{Display, FromStr};
-use std::collections::VecDeque;
+use advent_of_code::helpers::Snafu;
 
 pub fn part_one(input: &str) -> Option<String> {
     let nums = parse_input(input);
-    let num: Number = nums.iter().fold(Number::zero(), |acc, x| acc + x.clone());
-    Some(format!("{}", num))
+    let num: Snafu = nums.into_iter().sum();
+    Some(num.to_string())
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 25);
+    let input = &advent_of_code::read_input(25);
     advent_of_code::solve!(1, part_one, input);
 }
 
-#[derive(PartialEq, Eq, Debug, Display, FromStr, Clone, Copy)]
-enum Digit {
-    #[display("2")]
-    Two,
-    #[display("1")]
-    One,
-    #[display("0")]
-    Zero,
-    #[display("-")]
-    Minus,
-    #[display("=")]
-    DoubleMinus,
-}
-
-use crate::Digit::*;
-
-impl Digit {
-    fn to_i32(self) -> i32 {
-        match self {
-            Two => 2,
-            One => 1,
-            Zero => 0,
-            Minus => -1,
-            DoubleMinus => -2,
-        }
-    }
-}
-
-#[repr(transparent)]
-#[derive(PartialEq, Eq, Debug, Clone)]
-struct Number {
-    digits: Vec<Digit>,
-}
-
-impl Number {
-    fn zero() -> Self {
-        Self { digits: vec![Zero] }
-    }
-}
-
-impl std::fmt::Display for Number {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.digits
-                .iter()
-                .map(|d| d.to_string())
-                .collect::<String>()
-        )
-    }
-}
-
-fn parse_input(input: &str) -> Vec<Number> {
+fn parse_input(input: &str) -> Vec<Snafu> {
     input
         .lines()
-        .map(|line| Number {
-            digits: line
-                .chars()
-                .map(|c| c.to_string().parse().unwrap())
-                .collect(),
-        })
-        .collect()
-}
-
-impl std::ops::Add for Number {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        let pairs: Vec<(Digit, Digit)> = rev_and_zip_all_digits(&self, &other);
-
-        let mut result = VecDeque::new();
-
-        let carry = pairs
-            .iter()
-            .fold(0, |acc, (x, y)| match x.to_i32() + y.to_i32() + acc {
-                5 => {
-                    result.push_front(Zero);
-                    1
-                }
-                4 => {
-                    // 4 is 5 - 1
-                    result.push_front(Minus);
-                    1
-                }
-                3 => {
-                    // 3 is 5 - 2
-                    result.push_front(DoubleMinus);
-                    1
-                }
-                2 => {
-                    result.push_front(Two);
-                    0
-                }
-                1 => {
-                    result.push_front(One);
-                    0
-                }
-                0 => {
-                    result.push_front(Zero);
-                    0
-                }
-                -1 => {
-                    result.push_front(Minus);
-                    0
-                }
-                -2 => {
-                    result.push_front(DoubleMinus);
-                    0
-                }
-                -3 => {
-                    // -3 is -5 + 2
-                    result.push_front(Two);
-                    -1
-                }
-                -4 => {
-                    // -4 is -5 + 1
-                    result.push_front(One);
-                    -1
-                }
-                -5 => {
-                    result.push_front(Zero);
-                    -1
-                }
-                other => panic!("received {} when adding", other),
-            });
-
-        match carry {
-            -1 => result.push_front(Minus),
-            1 => result.push_front(One),
-            _ => (),
-        }
-
-        Number {
-            digits: result.iter().copied().collect(),
-        }
-    }
-}
-
-fn rev_and_zip_all_digits(fst: &Number, snd: &Number) -> Vec<(Digit, Digit)> {
-    fst.digits
-        .iter()
-        .rev()
-        .zip_longest(snd.digits.iter().rev())
-        .map(|x| match x {
-            Both(a, b) => (*a, *b),
-            Left(a) => (*a, Zero),
-            Right(b) => (Zero, *b),
-        })
+        .map(|line| line.parse().expect("did not find a SNAFU number"))
         .collect()
 }
 
@@ -170,38 +22,44 @@ fn rev_and_zip_all_digits(fst: &Number, snd: &Number) -> Vec<(Digit, Digit)> {
 mod tests {
     use super::*;
 
-    macro_rules! num [
-        ($($e:expr),*) => {
-            Number { digits: vec![$($e),*] }
-        }
-    ];
-
     #[test]
     fn test_parse() {
         let input = advent_of_code::read_file("examples", 25);
+        let nums: Vec<i128> = parse_input(&input)
+            .into_iter()
+            .map(|num| num.try_into().unwrap())
+            .collect();
         assert_eq!(
-            parse_input(&input),
-            [
-                num![One, DoubleMinus, Minus, Zero, Minus, Two],
-                num![One, Two, One, One, One],
-                num![Two, DoubleMinus, Zero, DoubleMinus],
-                num![Two, One],
-                num![Two, DoubleMinus, Zero, One],
-                num![One, One, One],
-                num![Two, Zero, Zero, One, Two],
-                num![One, One, Two],
-                num![One, DoubleMinus, Minus, One, DoubleMinus],
-                num![One, Minus, One, Two],
-                num![One, Two],
-                num![One, DoubleMinus],
-                num![One, Two, Two]
-            ]
+            nums,
+            [1747, 906, 198, 11, 201, 31, 1257, 32, 353, 107, 7, 3, 37]
         );
     }
 
     #[test]
     fn test_sum() {
-        assert_eq!(num![One] + num![One, Two], num![Two, DoubleMinus])
+        assert_eq!(
+            Snafu::from(1) + "12".parse().unwrap(),
+            "2=".parse().unwrap()
+        )
+    }
+
+    #[cfg(feature = "generators")]
+    fn to_i128(number: &Snafu) -> i128 {
+        number.try_into().unwrap()
+    }
+
+    #[cfg(feature = "generators")]
+    #[test]
+    fn test_sum_property() {
+        advent_of_code::generators::check_property(25, 20, |input| {
+            let nums = parse_input(input);
+            for (a, b) in nums.iter().zip(nums.iter().skip(1)) {
+                assert_eq!(to_i128(&(a.clone() + b.clone())), to_i128(a) + to_i128(b));
+            }
+
+            let total: Snafu = nums.iter().cloned().sum();
+            assert_eq!(to_i128(&total), nums.iter().map(to_i128).sum::<i128>());
+        });
     }
 
     #[test]
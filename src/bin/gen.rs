@@ -0,0 +1,74 @@
+/*
+ * Writes a random but valid puzzle input for a day, see `src/generators.rs`.
+ * The same day, scale and seed always produce the same file.
+ */
+use advent_of_code::generators;
+use std::path::PathBuf;
+use std::{fs, process};
+
+struct Args {
+    day: u8,
+    scale: usize,
+    seed: u64,
+    out: PathBuf,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        scale: args.opt_value_from_str("--scale")?.unwrap_or(100),
+        seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+        out: args
+            .opt_value_from_str("--out")?
+            .unwrap_or_else(|| PathBuf::from("src/generated")),
+        day: args.free_from_str()?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            eprintln!("example: `cargo gen 20 --scale 5000 --seed 1`");
+            process::exit(1);
+        }
+    };
+
+    let mut rng = generators::rng_from_seed(args.seed);
+    let input = match generators::generate(args.day, &mut rng, args.scale) {
+        Some(input) => input,
+        None => {
+            eprintln!("No generator for day {}.", args.day);
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = fs::create_dir_all(&args.out) {
+        eprintln!("Failed to create directory {}: {}", args.out.display(), e);
+        process::exit(1);
+    }
+
+    let path = args.out.join(format!("{:02}.txt", args.day));
+    match fs::write(&path, input) {
+        Ok(_) => {
+            println!(
+                "Generated input for day {} (scale: {}, seed: {}) at \"{}\"",
+                args.day,
+                args.scale,
+                args.seed,
+                path.display()
+            );
+            println!("---");
+            println!(
+                "🎄 Type `cargo solve {:02} --release -- --inputs {}` to run your solution against it.",
+                args.day,
+                args.out.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to write input file: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+/*
+ * Answers of `cargo all`, stored per day under `target/aoc-cache/`.
+ * An entry is keyed by the input file, the solver sources, the build profile and the cargo
+ * features, so changing any of them recomputes the day.
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CACHE_DIR: &str = "target/aoc-cache";
+
+// FNV-1a: unlike `DefaultHasher`, stable across Rust versions and runs.
+fn hash(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |acc, b| {
+        (acc ^ *b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+// every `.rs` file below `dir`, in a stable order.
+fn rust_files(dir: &Path, skip: &Path) -> Vec<PathBuf> {
+    let mut result = vec![];
+    let Ok(entries) = fs::read_dir(dir) else {
+        return result;
+    };
+
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path == skip {
+            continue;
+        }
+        if path.is_dir() {
+            result.extend(rust_files(&path, skip));
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            result.push(path);
+        }
+    }
+
+    result.sort();
+    result
+}
+
+/// The key of `day` solved from `input_path` with `profile` and the cargo `features`,
+/// `None` if the input can't be read.
+/// Solver sources are the day's module, the library modules it may use and `Cargo.toml`.
+pub fn key(
+    root: &Path,
+    day: u8,
+    input_path: &Path,
+    profile: &str,
+    features: &[&str],
+) -> Option<u64> {
+    let input = fs::read(input_path).ok()?;
+    let src = root.join("src");
+
+    let mut sources = vec![src.join("bin").join(format!("{:02}.rs", day))];
+    sources.extend(rust_files(&src, &src.join("bin")));
+    sources.push(root.join("Cargo.toml"));
+
+    let mut result = hash(FNV_OFFSET, profile.as_bytes());
+    for feature in features {
+        // features may change the output, e.g. `alloc-stats` prints allocation counts.
+        result = hash(result, b"\0");
+        result = hash(result, feature.as_bytes());
+    }
+    result = hash(result, &input);
+    for path in sources {
+        result = hash(result, path.to_string_lossy().as_bytes());
+        result = hash(result, &fs::read(&path).unwrap_or_default());
+    }
+    Some(result)
+}
+
+fn entry_path(root: &Path, day: u8, key: u64) -> PathBuf {
+    root.join(CACHE_DIR)
+        .join(format!("{:02}-{:016x}.txt", day, key))
+}
+
+/// The output stored for `day` under `key`.
+pub fn load(root: &Path, day: u8, key: u64) -> Option<String> {
+    fs::read_to_string(entry_path(root, day, key)).ok()
+}
+
+/// Stores `output` for `day` under `key`, replacing older entries of that day.
+pub fn store(root: &Path, day: u8, key: u64, output: &str) -> std::io::Result<()> {
+    let dir = root.join(CACHE_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let prefix = format!("{:02}-", day);
+    for entry in fs::read_dir(&dir)?.filter_map(|e| e.ok()) {
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    fs::write(entry_path(root, day, key), output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn scratch_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-cache-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src").join("bin")).unwrap();
+        fs::write(root.join("src").join("lib.rs"), "pub mod helpers;").unwrap();
+        fs::write(root.join("src").join("bin").join("01.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("src").join("bin").join("02.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("input.txt"), "1\n2\n").unwrap();
+        root
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(FNV_OFFSET, b""), FNV_OFFSET);
+        assert_eq!(hash(FNV_OFFSET, b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_key() {
+        let root = scratch_root("key");
+        let input = root.join("input.txt");
+        let entry_key = key(&root, 1, &input, "release", &[]).unwrap();
+
+        assert_eq!(key(&root, 1, &input, "release", &[]), Some(entry_key));
+        assert_ne!(key(&root, 1, &input, "debug", &[]), Some(entry_key));
+        assert_eq!(
+            key(&root, 1, &root.join("missing.txt"), "release", &[]),
+            None
+        );
+
+        // other days don't invalidate the entry, library code does.
+        fs::write(root.join("src").join("bin").join("02.rs"), "fn main() { }").unwrap();
+        assert_eq!(key(&root, 1, &input, "release", &[]), Some(entry_key));
+        fs::write(root.join("src").join("lib.rs"), "pub mod helpers; ").unwrap();
+        assert_ne!(key(&root, 1, &input, "release", &[]), Some(entry_key));
+
+        fs::write(&input, "1\n3\n").unwrap();
+        assert_ne!(key(&root, 1, &input, "release", &[]), Some(entry_key));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_key_features() {
+        let root = scratch_root("features");
+        let input = root.join("input.txt");
+
+        let plain = key(&root, 1, &input, "release", &[]);
+        let counted = key(&root, 1, &input, "release", &["alloc-stats"]);
+        assert!(plain.is_some() && counted.is_some());
+        assert_ne!(plain, counted);
+        assert_eq!(key(&root, 1, &input, "release", &["alloc-stats"]), counted);
+        assert_ne!(
+            key(&root, 1, &input, "release", &["alloc", "-stats"]),
+            counted
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_load_and_store() {
+        let root = scratch_root("store");
+        assert_eq!(load(&root, 1, 42), None);
+
+        store(&root, 1, 42, "answer").unwrap();
+        store(&root, 2, 42, "other").unwrap();
+        assert_eq!(load(&root, 1, 42), Some("answer".to_string()));
+
+        store(&root, 1, 43, "new answer").unwrap();
+        assert_eq!(load(&root, 1, 42), None);
+        assert_eq!(load(&root, 1, 43), Some("new answer".to_string()));
+        assert_eq!(load(&root, 2, 42), Some("other".to_string()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
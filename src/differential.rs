@@ -0,0 +1,146 @@
+/*
+ * Runs alternate implementations of the same part side by side.
+ * The first variant is the reference: the others are checked and timed against it.
+ * Example: `cargo solve 15 --release -- --compare`, optionally with `--inputs src/generated`.
+ */
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::fmt::{Debug, Display};
+use std::time::{Duration, Instant};
+
+/// A named implementation of a part.
+pub type Variant<T> = (&'static str, fn(&str) -> Option<T>);
+
+pub struct Run<T> {
+    pub name: &'static str,
+    pub result: Option<T>,
+    pub elapsed: Duration,
+}
+
+pub fn run_all<T>(variants: &[Variant<T>], input: &str) -> Vec<Run<T>> {
+    variants
+        .iter()
+        .map(|(name, solver)| {
+            let timer = Instant::now();
+            let result = solver(input);
+            Run {
+                name,
+                result,
+                elapsed: timer.elapsed(),
+            }
+        })
+        .collect()
+}
+
+/// Panics with the names of both variants if any of them disagrees with the first one.
+pub fn assert_agree<T: PartialEq + Debug>(variants: &[Variant<T>], input: &str) {
+    let runs = run_all(variants, input);
+    let (reference, others) = runs.split_first().expect("no variants to compare");
+
+    for run in others {
+        assert_eq!(
+            run.result, reference.result,
+            "\"{}\" disagrees with \"{}\"",
+            run.name, reference.name
+        );
+    }
+}
+
+/// Whether `--compare` was passed on the command line.
+pub fn is_requested() -> bool {
+    pico_args::Arguments::from_env().contains("--compare")
+}
+
+/// Prints every result with its speed relative to the reference, returns false on disagreement.
+pub fn print_report<T: Display + PartialEq>(
+    part: u8,
+    variants: &[Variant<T>],
+    input: &str,
+) -> bool {
+    let runs = run_all(variants, input);
+    let Some((reference, _)) = runs.split_first() else {
+        return true;
+    };
+
+    println!(
+        "🔍 {}Part {}{}: comparing {} variants 🔍",
+        ANSI_BOLD,
+        part,
+        ANSI_RESET,
+        runs.len()
+    );
+
+    let mut agree = true;
+    for run in runs.iter() {
+        let result = match &run.result {
+            Some(result) => result.to_string(),
+            None => "not solved.".to_string(),
+        };
+        // avoid "elapsed:" here, `cargo all` sums up every line containing it.
+        let ratio = run.elapsed.as_secs_f64() / reference.elapsed.as_secs_f64().max(1e-9);
+        let verdict = if run.result == reference.result {
+            String::new()
+        } else {
+            agree = false;
+            format!(" ✗ disagrees with \"{}\"", reference.name)
+        };
+
+        println!(
+            "{}: {} {}(took: {:.2?}, {:.2}x){}{}",
+            run.name, result, ANSI_ITALIC, run.elapsed, ratio, ANSI_RESET, verdict
+        );
+    }
+
+    agree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum(input: &str) -> Option<u32> {
+        input.lines().map(|l| l.parse::<u32>().ok()).sum()
+    }
+
+    fn sum_fold(input: &str) -> Option<u32> {
+        input
+            .lines()
+            .try_fold(0, |acc, l| Some(acc + l.parse::<u32>().ok()?))
+    }
+
+    fn count(input: &str) -> Option<u32> {
+        Some(input.lines().count() as u32)
+    }
+
+    #[test]
+    fn test_run_all() {
+        let runs = run_all(&[("sum", sum), ("count", count)], "1\n2\n3\n");
+        assert_eq!(runs[0].name, "sum");
+        assert_eq!(runs[0].result, Some(6));
+        assert_eq!(runs[1].result, Some(3));
+    }
+
+    #[test]
+    fn test_assert_agree() {
+        assert_agree(&[("sum", sum), ("sum_fold", sum_fold)], "1\n2\n3\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"count\" disagrees with \"sum\"")]
+    fn test_assert_agree_disagreement() {
+        assert_agree(&[("sum", sum), ("count", count)], "1\n2\n3\n");
+    }
+
+    #[test]
+    fn test_print_report() {
+        assert!(print_report(
+            1,
+            &[("sum", sum), ("sum_fold", sum_fold)],
+            "4\n5\n"
+        ));
+        assert!(!print_report(
+            1,
+            &[("sum", sum), ("count", count)],
+            "4\n5\n"
+        ));
+    }
+}
@@ -0,0 +1,1077 @@
+/*
+ * Random but valid puzzle inputs, one generator per day.
+ * Generators are deterministic for a given seed, and the size of their output grows with `scale`.
+ * Example: `advent_of_code::generators::generate(8, &mut rng_from_seed(42), 10)`.
+ */
+use crate::helpers::num::lcm;
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+
+pub type GenRng = ChaCha8Rng;
+
+pub fn rng_from_seed(seed: u64) -> GenRng {
+    GenRng::seed_from_u64(seed)
+}
+
+pub fn generate(day: u8, rng: &mut GenRng, scale: usize) -> Option<String> {
+    let scale = scale.max(1);
+    let input = match day {
+        1 => day01(rng, scale),
+        2 => day02(rng, scale),
+        3 => day03(rng, scale),
+        4 => day04(rng, scale),
+        5 => day05(rng, scale),
+        6 => day06(rng, scale),
+        7 => day07(rng, scale),
+        8 => day08(rng, scale),
+        9 => day09(rng, scale),
+        10 => day10(rng, scale),
+        11 => day11(rng, scale),
+        12 => day12(rng, scale),
+        13 => day13(rng, scale),
+        14 => day14(rng, scale),
+        15 => day15(rng, scale),
+        16 => day16(rng, scale),
+        17 => day17(rng, scale),
+        18 => day18(rng, scale),
+        19 => day19(rng, scale),
+        20 => day20(rng, scale),
+        21 => day21(rng, scale),
+        22 => day22(rng, scale),
+        23 => day23(rng, scale),
+        24 => day24(rng, scale),
+        25 => day25(rng, scale),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Runs `property` against generated inputs for `day`, with a scale picked between 1 and `max_scale`.
+/// `AOC_PROPERTY_CASES` sets the number of cases (default: 64), `AOC_PROPERTY_SEED` replays a single one.
+pub fn check_property<F: Fn(&str)>(day: u8, max_scale: usize, property: F) {
+    let seeds: Vec<u64> = match env::var("AOC_PROPERTY_SEED") {
+        Ok(seed) => vec![seed
+            .parse()
+            .expect("AOC_PROPERTY_SEED should be an integer")],
+        Err(_) => {
+            let cases = env::var("AOC_PROPERTY_CASES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(64);
+            (0..cases).collect()
+        }
+    };
+
+    for seed in seeds {
+        let mut rng = rng_from_seed(seed);
+        let scale = rng.gen_range(1..=max_scale.max(1));
+        let input = generate(day, &mut rng, scale).expect("no generator for this day");
+
+        if let Err(err) = panic::catch_unwind(AssertUnwindSafe(|| property(&input))) {
+            eprintln!(
+                "property failed for day {:02} (seed: {}, scale: {}), replay with AOC_PROPERTY_SEED={}",
+                day, seed, scale, seed
+            );
+            panic::resume_unwind(err);
+        }
+    }
+}
+
+// a size between half of `scale` and `scale`, so that dimensions vary between cases.
+fn around(rng: &mut impl Rng, scale: usize) -> usize {
+    rng.gen_range(scale.div_ceil(2)..=scale).max(1)
+}
+
+fn lowercase_name<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
+    (0..len)
+        .map(|_| rng.gen_range(b'a'..=b'z') as char)
+        .collect()
+}
+
+fn unique_names(
+    rng: &mut impl Rng,
+    count: usize,
+    mut new_name: impl FnMut(&mut dyn rand::RngCore) -> String,
+    taken: &[&str],
+) -> Vec<String> {
+    let mut seen: HashSet<String> = taken.iter().map(|s| s.to_string()).collect();
+    let mut result = Vec::with_capacity(count);
+
+    while result.len() < count {
+        let name = new_name(rng);
+        if seen.insert(name.clone()) {
+            result.push(name);
+        }
+    }
+
+    result
+}
+
+/// `scale` elves carrying up to eight items each.
+pub fn day01(rng: &mut impl Rng, scale: usize) -> String {
+    let elves = (0..scale).map(|_| {
+        let items = rng.gen_range(1..=8);
+        (0..items).map(|_| rng.gen_range(1000..=60000)).join("\n")
+    });
+    elves.collect::<Vec<_>>().join("\n\n") + "\n"
+}
+
+/// `scale` rounds of rock paper scissors.
+pub fn day02(rng: &mut impl Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                ["A", "B", "C"].choose(rng).unwrap(),
+                ["X", "Y", "Z"].choose(rng).unwrap()
+            )
+        })
+        .collect()
+}
+
+/// `scale` groups of three rucksacks, sharing exactly one badge per group.
+pub fn day03(rng: &mut impl Rng, scale: usize) -> String {
+    let mut result = String::new();
+
+    for _ in 0..scale {
+        let mut letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        letters.shuffle(rng);
+        let badge = letters[0];
+
+        for private in letters[1..].chunks(17) {
+            let shared = if rng.gen_bool(0.25) {
+                badge
+            } else {
+                private[0]
+            };
+            let rest: Vec<char> = private.iter().copied().filter(|c| *c != shared).collect();
+            let (left_pool, right_pool) = rest.split_at(rest.len() / 2);
+            let half = rng.gen_range(2..=12);
+
+            let mut left = vec![shared];
+            let mut right = vec![shared];
+            if shared != badge {
+                if rng.gen_bool(0.5) {
+                    left.push(badge)
+                } else {
+                    right.push(badge)
+                }
+            }
+            while left.len() < half {
+                left.push(*left_pool.choose(rng).unwrap());
+            }
+            while right.len() < half {
+                right.push(*right_pool.choose(rng).unwrap());
+            }
+
+            left.shuffle(rng);
+            right.shuffle(rng);
+            result.extend(left.iter().chain(right.iter()));
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// `scale` pairs of section assignments.
+pub fn day04(rng: &mut impl Rng, scale: usize) -> String {
+    let range = |rng: &mut dyn rand::RngCore| {
+        let start = rng.gen_range(1..=99);
+        (start, rng.gen_range(start..=99))
+    };
+
+    (0..scale)
+        .map(|_| {
+            let (a, b) = range(rng);
+            let (c, d) = range(rng);
+            format!("{}-{},{}-{}\n", a, b, c, d)
+        })
+        .collect()
+}
+
+/// Up to nine stacks and `scale` moves, none of which empties a stack.
+pub fn day05(rng: &mut impl Rng, scale: usize) -> String {
+    let count = rng.gen_range(2..=9);
+    let mut stacks: Vec<Vec<char>> = (0..count)
+        .map(|i| {
+            let height = rng.gen_range(if i == 0 { 2 } else { 1 }..=8);
+            (0..height)
+                .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+                .collect()
+        })
+        .collect();
+
+    let initial = stacks.clone();
+    let mut moves = vec![];
+    for _ in 0..scale {
+        let candidates: Vec<usize> = (0..count).filter(|i| stacks[*i].len() > 1).collect();
+        let from = *candidates.choose(rng).unwrap();
+        let to = (from + rng.gen_range(1..count)) % count;
+        let amount = rng.gen_range(1..stacks[from].len());
+
+        let split_at = stacks[from].len() - amount;
+        let moved = stacks[from].split_off(split_at);
+        stacks[to].extend(moved.iter().rev());
+        moves.push(format!("move {} from {} to {}", amount, from + 1, to + 1));
+    }
+
+    let stacks = initial;
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    let mut result = String::new();
+    for row in (0..height).rev() {
+        let line = stacks
+            .iter()
+            .map(|s| match s.get(row) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            })
+            .join(" ");
+        result.push_str(&line);
+        result.push('\n');
+    }
+    result.push_str(&(1..=count).map(|i| format!(" {} ", i)).join(" "));
+    result.push_str("\n\n");
+    result.push_str(&moves.join("\n"));
+    result.push('\n');
+    result
+}
+
+/// A datastream of about `scale` characters containing at least one 14-character marker.
+pub fn day06(rng: &mut impl Rng, scale: usize) -> String {
+    let mut alphabet: Vec<char> = ('a'..='z').collect();
+    let mut result: String = (0..scale)
+        .map(|_| rng.gen_range(b'a'..=b'h') as char)
+        .collect();
+
+    alphabet.shuffle(rng);
+    result.extend(&alphabet[..14]);
+    result.extend((0..rng.gen_range(1..=10)).map(|_| rng.gen_range(b'a'..=b'z') as char));
+    result.push('\n');
+    result
+}
+
+struct Directory {
+    files: Vec<(String, u32)>,
+    children: Vec<(String, usize)>,
+}
+
+/// A terminal session exploring `scale` directories, totalling more than 40000000 bytes.
+pub fn day07(rng: &mut impl Rng, scale: usize) -> String {
+    let max_size = (1_000_000_000 / (4 * scale as u32)).clamp(1, 300_000);
+    let mut dirs: Vec<Directory> = vec![];
+    let mut depths: Vec<usize> = vec![];
+
+    for index in 0..scale {
+        dirs.push(Directory {
+            files: vec![],
+            children: vec![],
+        });
+        depths.push(0);
+
+        if index > 0 {
+            let parent = loop {
+                let candidate = rng.gen_range(0..index);
+                if depths[candidate] < 10 {
+                    break candidate;
+                }
+            };
+            let taken: Vec<&str> = dirs[parent]
+                .children
+                .iter()
+                .map(|(n, _)| n.as_str())
+                .collect();
+            let name = unique_names(
+                rng,
+                1,
+                |r| {
+                    let len = r.gen_range(1..=6);
+                    lowercase_name(r, len)
+                },
+                &taken,
+            )
+            .remove(0);
+            dirs[parent].children.push((name, index));
+            depths[index] = depths[parent] + 1;
+        }
+    }
+
+    let mut total: u32 = 0;
+    for dir in dirs.iter_mut() {
+        let min_files = if dir.children.is_empty() { 1 } else { 0 };
+        let count = rng.gen_range(min_files..=4);
+        let names = unique_names(
+            rng,
+            count,
+            |r| {
+                let ext = ["", ".txt", ".dat", ".lst"].choose(r).unwrap();
+                let len = r.gen_range(1..=6);
+                lowercase_name(r, len) + ext
+            },
+            &[],
+        );
+        for name in names {
+            let size = rng.gen_range(1..=max_size);
+            total += size;
+            dir.files.push((name, size));
+        }
+    }
+
+    if total <= 40_000_000 {
+        let taken: Vec<&str> = dirs[0].files.iter().map(|(n, _)| n.as_str()).collect();
+        let name = unique_names(rng, 1, |r| lowercase_name(r, 8) + ".bin", &taken).remove(0);
+        dirs[0]
+            .files
+            .push((name, 40_000_001 - total + rng.gen_range(0..5_000_000)));
+    }
+
+    fn explore(dirs: &[Directory], index: usize, rng: &mut impl Rng, lines: &mut Vec<String>) {
+        let dir = &dirs[index];
+        let mut entries: Vec<String> = dir
+            .children
+            .iter()
+            .map(|(name, _)| format!("dir {}", name))
+            .chain(
+                dir.files
+                    .iter()
+                    .map(|(name, size)| format!("{} {}", size, name)),
+            )
+            .collect();
+        entries.shuffle(rng);
+
+        lines.push("$ ls".to_string());
+        lines.extend(entries);
+
+        for (name, child) in &dir.children {
+            lines.push(format!("$ cd {}", name));
+            explore(dirs, *child, rng, lines);
+            lines.push("$ cd ..".to_string());
+        }
+    }
+
+    let mut lines = vec!["$ cd /".to_string()];
+    explore(&dirs, 0, rng, &mut lines);
+    lines.join("\n") + "\n"
+}
+
+/// A forest of at most `scale` by `scale` trees, not necessarily square.
+pub fn day08(rng: &mut impl Rng, scale: usize) -> String {
+    let rows = around(rng, scale);
+    let cols = around(rng, scale);
+
+    (0..rows)
+        .map(|_| {
+            let mut line: String = (0..cols)
+                .map(|_| char::from_digit(rng.gen_range(0..=9), 10).unwrap())
+                .collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+/// `scale` rope motions.
+pub fn day09(rng: &mut impl Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                ["R", "D", "L", "U"].choose(rng).unwrap(),
+                rng.gen_range(1..=20)
+            )
+        })
+        .collect()
+}
+
+/// A program running for at least 240 cycles, or `scale` cycles if that is more.
+pub fn day10(rng: &mut impl Rng, scale: usize) -> String {
+    let mut register_x: i32 = 1;
+    let mut cycles = 0;
+    let mut lines = vec![];
+
+    while cycles < scale.max(240) {
+        if rng.gen_bool(0.3) {
+            lines.push("noop".to_string());
+            cycles += 1;
+        } else {
+            let target = rng.gen_range(0..40);
+            lines.push(format!("addx {}", target - register_x));
+            register_x = target;
+            cycles += 2;
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Up to eight monkeys holding up to `scale` items each.
+pub fn day11(rng: &mut impl Rng, scale: usize) -> String {
+    let count = rng.gen_range(2..=8);
+    let mut divisors = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+    divisors.shuffle(rng);
+
+    (0..count)
+        .map(|id| {
+            let items = (0..rng.gen_range(1..=scale))
+                .map(|_| rng.gen_range(50..=99))
+                .join(", ");
+            let op = match rng.gen_range(0..6) {
+                0 => "old * old".to_string(),
+                1 | 2 => format!("old * {}", rng.gen_range(2..=19)),
+                _ => format!("old + {}", rng.gen_range(1..=9)),
+            };
+            let if_true = (id + rng.gen_range(1..count)) % count;
+            let if_false = loop {
+                let candidate = (id + rng.gen_range(1..count)) % count;
+                if candidate != if_true || count == 2 {
+                    break candidate;
+                }
+            };
+
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                id, items, op, divisors[id], if_true, if_false
+            )
+        })
+        .join("\n")
+}
+
+/// A heightmap of around `scale` rows where `E` can be reached from `S` and from every `a`.
+pub fn day12(rng: &mut impl Rng, scale: usize) -> String {
+    let rows = around(rng, scale).max(2);
+    let cols = 28 + around(rng, scale);
+
+    // sparse peaks, spread so that neighbouring heights never differ by more than one.
+    let mut heights: Vec<Vec<i32>> = (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| {
+                    if rng.gen_bool(0.05) {
+                        rng.gen_range(0..=25)
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    for y in 0..rows {
+        for x in 0..cols {
+            if y > 0 {
+                heights[y][x] = heights[y][x].max(heights[y - 1][x] - 1);
+            }
+            if x > 0 {
+                heights[y][x] = heights[y][x].max(heights[y][x - 1] - 1);
+            }
+        }
+    }
+    for y in (0..rows).rev() {
+        for x in (0..cols).rev() {
+            if y + 1 < rows {
+                heights[y][x] = heights[y][x].max(heights[y + 1][x] - 1);
+            }
+            if x + 1 < cols {
+                heights[y][x] = heights[y][x].max(heights[y][x + 1] - 1);
+            }
+        }
+    }
+
+    // `S` and its right neighbour sit at the bottom, `E`'s left neighbour at the top.
+    let start = (0, rng.gen_range(0..rows) as i32);
+    let end = (cols as i32 - 1, rng.gen_range(0..rows) as i32);
+    let distance = |(x1, y1): (i32, i32), (x2, y2): (i32, i32)| (x1 - x2).abs() + (y1 - y2).abs();
+
+    (0..rows)
+        .map(|y| {
+            let mut line: String = (0..cols)
+                .map(|x| {
+                    let pos = (x as i32, y as i32);
+                    if pos == start {
+                        'S'
+                    } else if pos == end {
+                        'E'
+                    } else {
+                        let from_start = distance(pos, start).min(distance(pos, (1, start.1)));
+                        let from_end = distance(pos, (end.0 - 1, end.1));
+                        let height = heights[y][x].min(from_start).max(25 - from_end);
+                        (b'a' + height.clamp(0, 25) as u8) as char
+                    }
+                })
+                .collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+fn packet(rng: &mut impl Rng, depth: u32) -> String {
+    let items = (0..rng.gen_range(0..=4))
+        .map(|_| {
+            if depth > 0 && rng.gen_bool(0.3) {
+                packet(rng, depth - 1)
+            } else {
+                rng.gen_range(0..=10).to_string()
+            }
+        })
+        .join(",");
+    format!("[{}]", items)
+}
+
+/// `scale` pairs of packets.
+pub fn day13(rng: &mut impl Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| format!("{}\n{}\n", packet(rng, 4), packet(rng, 4)))
+        .join("\n")
+}
+
+/// `scale` rock paths below the sand source.
+pub fn day14(rng: &mut impl Rng, scale: usize) -> String {
+    let spread = (10 + scale as i32).min(400);
+    let depth = (10 + scale as i32).min(150);
+
+    (0..scale)
+        .map(|_| {
+            let mut x = rng.gen_range(500 - spread..=500 + spread);
+            let mut y = rng.gen_range(1..=depth);
+            let mut points = vec![format!("{},{}", x, y)];
+
+            for segment in 0..rng.gen_range(1..=4) {
+                let length = rng.gen_range(1..=6) * if rng.gen_bool(0.5) { 1 } else { -1 };
+                if segment % 2 == 0 {
+                    x += length;
+                } else {
+                    y = (y + length).max(1);
+                }
+                points.push(format!("{},{}", x, y));
+            }
+
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+/// Sensors leaving exactly one uncovered position in the 4000000 square, plus `scale` decoys.
+pub fn day15(rng: &mut impl Rng, scale: usize) -> String {
+    let limit = 4_000_000;
+    let (hx, hy): (i64, i64) = (rng.gen_range(0..=limit), rng.gen_range(0..=limit));
+    let d = limit + rng.gen_range(1..=1000);
+    let distance = |(x1, y1): (i64, i64), (x2, y2): (i64, i64)| (x1 - x2).abs() + (y1 - y2).abs();
+
+    // straight sensors cover everything off the diagonals through the hole,
+    // and diagonal sensors cover the diagonals themselves.
+    let mut sensors: Vec<((i64, i64), (i64, i64))> = vec![];
+    for (dx, dy) in [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ] {
+        sensors.push(((hx + dx * d, hy + dy * d), (hx + dx, hy + dy)));
+    }
+
+    for _ in 0..scale {
+        for _ in 0..100 {
+            let sensor = (
+                rng.gen_range(-limit / 2..=limit * 3 / 2),
+                rng.gen_range(-limit / 2..=limit * 3 / 2),
+            );
+            let max_radius = (distance(sensor, (hx, hy)) - 1).min(limit / 4);
+            if max_radius < 1 {
+                continue;
+            }
+
+            let radius = rng.gen_range(1..=max_radius);
+            let dx = rng.gen_range(-radius..=radius);
+            let dy = (radius - dx.abs()) * if rng.gen_bool(0.5) { 1 } else { -1 };
+            let beacon = (sensor.0 + dx, sensor.1 + dy);
+
+            // every sensor must keep a unique closest beacon
+            let valid = sensors.iter().all(|(other, other_beacon)| {
+                distance(*other, beacon) > distance(*other, *other_beacon)
+                    && distance(sensor, *other_beacon) > radius
+            });
+            if valid {
+                sensors.push((sensor, beacon));
+                break;
+            }
+        }
+    }
+
+    sensors.shuffle(rng);
+    sensors
+        .iter()
+        .map(|((sx, sy), (bx, by))| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sx, sy, bx, by
+            )
+        })
+        .collect()
+}
+
+/// `scale` connected valves, about a third of which have a flow rate.
+pub fn day16(rng: &mut impl Rng, scale: usize) -> String {
+    let count = scale.clamp(2, 26 * 26);
+    let mut names = vec!["AA".to_string()];
+    names.extend(unique_names(
+        rng,
+        count - 1,
+        |r| (0..2).map(|_| r.gen_range(b'A'..=b'Z') as char).collect(),
+        &["AA"],
+    ));
+
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for index in 1..count {
+        connect(index, rng.gen_range(0..index));
+    }
+    for _ in 0..count / 3 {
+        connect(rng.gen_range(0..count), rng.gen_range(0..count));
+    }
+
+    let mut lines: Vec<String> = (0..count)
+        .map(|index| {
+            let flow_rate = if index > 0 && rng.gen_bool(0.35) {
+                rng.gen_range(1..=25)
+            } else {
+                0
+            };
+            let leads_to = tunnels[index].iter().map(|t| &names[*t]).join(", ");
+            if tunnels[index].len() == 1 {
+                format!(
+                    "Valve {} has flow rate={}; tunnel leads to valve {}",
+                    names[index], flow_rate, leads_to
+                )
+            } else {
+                format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    names[index], flow_rate, leads_to
+                )
+            }
+        })
+        .collect();
+
+    lines.shuffle(rng);
+    lines.join("\n") + "\n"
+}
+
+/// A jet pattern of `scale` pushes.
+pub fn day17(rng: &mut impl Rng, scale: usize) -> String {
+    let mut result: String = (0..scale)
+        .map(|_| if rng.gen_bool(0.5) { '<' } else { '>' })
+        .collect();
+    result.push('\n');
+    result
+}
+
+/// `scale` distinct cubes packed in a box filled to roughly a quarter.
+pub fn day18(rng: &mut impl Rng, scale: usize) -> String {
+    let side = ((scale as f64 * 3.5).cbrt().ceil() as i32).max(2);
+    let mut seen = HashSet::new();
+    let mut result = String::new();
+
+    while seen.len() < scale {
+        let cube = (
+            rng.gen_range(0..side),
+            rng.gen_range(0..side),
+            rng.gen_range(0..side),
+        );
+        if seen.insert(cube) {
+            result.push_str(&format!("{},{},{}\n", cube.0, cube.1, cube.2));
+        }
+    }
+
+    result
+}
+
+/// `scale` robot blueprints.
+pub fn day19(rng: &mut impl Rng, scale: usize) -> String {
+    (1..=scale)
+        .map(|id| {
+            format!(
+                "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+                id,
+                rng.gen_range(2..=4),
+                rng.gen_range(2..=4),
+                rng.gen_range(2..=4),
+                rng.gen_range(5..=20),
+                rng.gen_range(2..=4),
+                rng.gen_range(5..=20)
+            )
+        })
+        .collect()
+}
+
+/// `scale` non-zero numbers, some negative and some repeated, plus a single zero.
+pub fn day20(rng: &mut impl Rng, scale: usize) -> String {
+    let mut nums: Vec<i64> = (0..scale)
+        .map(|_| {
+            let value = rng.gen_range(1..=10000);
+            if rng.gen_bool(0.5) {
+                -value
+            } else {
+                value
+            }
+        })
+        .collect();
+    nums.insert(rng.gen_range(0..=nums.len()), 0);
+    nums.iter().join("\n") + "\n"
+}
+
+enum Job {
+    Lit(i64),
+    Expr(usize, char, usize),
+}
+
+// a subtree evaluating to `target` with positive literals and exact divisions.
+fn monkey_subtree(rng: &mut impl Rng, jobs: &mut Vec<Job>, target: i64, depth: u32) -> usize {
+    let job = if depth == 0 || (target <= 20 && rng.gen_bool(0.3)) {
+        Job::Lit(target)
+    } else {
+        let divisor = (2..=9).find(|d| target % d == 0 && target / d > 1);
+        let (lhs, op, rhs) = match (rng.gen_range(0..4), divisor) {
+            (0, _) if target < 1_000_000_000 => {
+                let rhs = rng.gen_range(2..=5);
+                (target * rhs, '/', rhs)
+            }
+            (1, Some(rhs)) => (target / rhs, '*', rhs),
+            (2, _) => {
+                let rhs = rng.gen_range(1..=20);
+                (target + rhs, '-', rhs)
+            }
+            _ if target >= 2 => {
+                let lhs = rng.gen_range(1..target);
+                (lhs, '+', target - lhs)
+            }
+            _ => {
+                let rhs = rng.gen_range(1..=20);
+                (target + rhs, '-', rhs)
+            }
+        };
+        let lhs = monkey_subtree(rng, jobs, lhs, depth - 1);
+        let rhs = monkey_subtree(rng, jobs, rhs, depth - 1);
+        Job::Expr(lhs, op, rhs)
+    };
+
+    jobs.push(job);
+    jobs.len() - 1
+}
+
+/// A tree of roughly `scale` monkeys, where `humn` appears once and linearly below `root`.
+pub fn day21(rng: &mut impl Rng, scale: usize) -> String {
+    let depth = (usize::BITS - scale.leading_zeros()).min(12);
+    let path_length = rng.gen_range(1..=scale.clamp(1, 20));
+
+    let (jobs, humn, root) = loop {
+        let mut jobs = vec![Job::Lit(rng.gen_range(1..=5000))];
+        let humn = 0;
+        let answer: i64 = rng.gen_range(1..=5000);
+
+        // (node, multiplier, offset): the node evaluates to multiplier * humn + offset
+        let (mut node, mut mul, mut add) = (humn, 1_i64, 0_i64);
+        for _ in 0..path_length {
+            let op = match rng.gen_range(0..4) {
+                0 if mul.abs() < 100_000 => '*',
+                0 | 1 => '+',
+                _ => '-',
+            };
+            let value = match op {
+                '*' => rng.gen_range(2..=4),
+                _ => rng.gen_range(1..=100),
+            };
+            let sibling = monkey_subtree(rng, &mut jobs, value, depth / 2);
+            let humn_first = op == '*' || rng.gen_bool(0.5);
+
+            (mul, add) = match (op, humn_first) {
+                ('*', _) => (mul * value, add * value),
+                ('+', _) => (mul, add + value),
+                (_, true) => (mul, add - value),
+                (_, false) => (-mul, value - add),
+            };
+            jobs.push(if humn_first {
+                Job::Expr(node, op, sibling)
+            } else {
+                Job::Expr(sibling, op, node)
+            });
+            node = jobs.len() - 1;
+        }
+
+        let other_side = mul * answer + add;
+        if other_side < 1 {
+            continue;
+        }
+        let other = monkey_subtree(rng, &mut jobs, other_side, depth);
+        jobs.push(if rng.gen_bool(0.5) {
+            Job::Expr(node, '+', other)
+        } else {
+            Job::Expr(other, '+', node)
+        });
+        let root = jobs.len() - 1;
+        break (jobs, humn, root);
+    };
+
+    let mut names = unique_names(rng, jobs.len(), |r| lowercase_name(r, 4), &["root", "humn"]);
+    names[humn] = "humn".to_string();
+    names[root] = "root".to_string();
+
+    let mut lines: Vec<String> = jobs
+        .iter()
+        .enumerate()
+        .map(|(index, job)| match job {
+            Job::Lit(value) => format!("{}: {}", names[index], value),
+            Job::Expr(lhs, op, rhs) => {
+                format!("{}: {} {} {}", names[index], names[*lhs], op, names[*rhs])
+            }
+        })
+        .collect();
+
+    lines.shuffle(rng);
+    lines.join("\n") + "\n"
+}
+
+// the eleven cube nets, in face units.
+const CUBE_NETS: [&[&str]; 11] = [
+    &["#...", "####", "#..."],
+    &["#...", "####", ".#.."],
+    &["#...", "####", "..#."],
+    &["#...", "####", "...#"],
+    &[".#..", "####", ".#.."],
+    &[".#..", "####", "..#."],
+    &["#...", "###.", "..##"],
+    &[".#..", "###.", "..##"],
+    &["..#.", "###.", "..##"],
+    &["##..", ".##.", "..##"],
+    &["###..", "..###"],
+];
+
+/// A cube net with faces of around `scale` tiles, and a path turning `scale` times.
+pub fn day22(rng: &mut impl Rng, scale: usize) -> String {
+    let size = around(rng, scale) as i32;
+
+    let mut faces: Vec<(i32, i32)> = CUBE_NETS
+        .choose(rng)
+        .unwrap()
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x as i32, y as i32))
+        })
+        .collect();
+    if rng.gen_bool(0.5) {
+        faces.iter_mut().for_each(|f| f.0 = -f.0);
+    }
+    for _ in 0..rng.gen_range(0..4) {
+        faces.iter_mut().for_each(|f| *f = (-f.1, f.0));
+    }
+    let min_x = faces.iter().map(|f| f.0).min().unwrap();
+    let min_y = faces.iter().map(|f| f.1).min().unwrap();
+    faces
+        .iter_mut()
+        .for_each(|f| *f = (f.0 - min_x, f.1 - min_y));
+
+    let height = faces.iter().map(|f| f.1).max().unwrap() + 1;
+    let width = faces.iter().map(|f| f.0).max().unwrap() + 1;
+    let mut start = None;
+
+    let mut result = String::new();
+    for y in 0..height * size {
+        let mut line = String::new();
+        for x in 0..width * size {
+            if faces.contains(&(x / size, y / size)) {
+                if start.is_none() {
+                    start = Some((x, y));
+                    line.push('.');
+                } else {
+                    line.push(if rng.gen_bool(0.1) { '#' } else { '.' });
+                }
+            } else {
+                line.push(' ');
+            }
+        }
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    result.push('\n');
+    result.push_str(&rng.gen_range(1..=2 * size).to_string());
+    for _ in 0..scale {
+        result.push(if rng.gen_bool(0.5) { 'R' } else { 'L' });
+        result.push_str(&rng.gen_range(1..=2 * size).to_string());
+    }
+    result.push('\n');
+    result
+}
+
+/// A grove of around `scale` by `scale` tiles, half of them with an elf.
+pub fn day23(rng: &mut impl Rng, scale: usize) -> String {
+    let rows = around(rng, scale).max(2);
+    let cols = around(rng, scale);
+    let mut grid: Vec<Vec<char>> = (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| if rng.gen_bool(0.5) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    // at least two elves
+    grid[0][0] = '#';
+    grid[rows - 1][cols - 1] = '#';
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+// whether the expedition can cross the valley, by simulating every reachable position at once.
+fn valley_is_crossable(width: usize, height: usize, blizzards: &[(usize, usize, char)]) -> bool {
+    let period = lcm(width, height);
+    let end = (width, height + 1);
+    let mut frontier = vec![(1, 0)];
+    let mut next = vec![vec![false; width + 2]; height + 2];
+
+    for time in 1..=(2 * period + 2 * (width + height)) {
+        let mut occupied = vec![vec![false; width + 2]; height + 2];
+        for &(x, y, dir) in blizzards {
+            let (x, y) = match dir {
+                '>' => ((x - 1 + time) % width + 1, y),
+                '<' => ((x - 1 + width - time % width) % width + 1, y),
+                'v' => (x, (y - 1 + time) % height + 1),
+                _ => (x, (y - 1 + height - time % height) % height + 1),
+            };
+            occupied[y][x] = true;
+        }
+
+        let mut reached = vec![];
+        for &(x, y) in &frontier {
+            let (x, y): (usize, usize) = (x, y);
+            for (nx, ny) in [
+                (x, y),
+                (x + 1, y),
+                (x - 1, y),
+                (x, y + 1),
+                (x, y.wrapping_sub(1)),
+            ] {
+                let inside = (1..=width).contains(&nx) && (1..=height).contains(&ny);
+                if (nx, ny) == end {
+                    return true;
+                }
+                if ((nx, ny) == (1, 0) || inside) && !occupied[ny][nx] && !next[ny][nx] {
+                    next[ny][nx] = true;
+                    reached.push((nx, ny));
+                }
+            }
+        }
+        reached.iter().for_each(|&(x, y)| next[y][x] = false);
+        frontier = reached;
+    }
+
+    false
+}
+
+/// A valley of around `scale` by `scale` tiles that can be crossed.
+pub fn day24(rng: &mut impl Rng, scale: usize) -> String {
+    let width = around(rng, scale).max(2);
+    let height = around(rng, scale).max(2);
+    let mut density = 0.3;
+
+    let blizzards = loop {
+        let mut blizzards = vec![];
+        for y in 1..=height {
+            for x in 1..=width {
+                if rng.gen_bool(density) {
+                    // vertical blizzards would blow into the entrance or the exit
+                    let dirs: &[char] = if x == 1 || x == width {
+                        &['<', '>']
+                    } else {
+                        &['<', '>', '^', 'v']
+                    };
+                    blizzards.push((x, y, *dirs.choose(rng).unwrap()));
+                }
+            }
+        }
+
+        if valley_is_crossable(width, height, &blizzards) {
+            break blizzards;
+        }
+        density /= 2.0;
+    };
+
+    let mut grid = vec![vec!['#'; width + 2]; height + 2];
+    for row in grid.iter_mut().take(height + 1).skip(1) {
+        row[1..=width].iter_mut().for_each(|c| *c = '.');
+    }
+    grid[0][1] = '.';
+    grid[height + 1][width] = '.';
+    for (x, y, dir) in blizzards {
+        grid[y][x] = dir;
+    }
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+fn to_snafu(mut value: i64) -> String {
+    let mut digits = vec![];
+    while value != 0 {
+        let (digit, carry) = match value.rem_euclid(5) {
+            3 => ('=', 1),
+            4 => ('-', 1),
+            d => (char::from_digit(d as u32, 10).unwrap(), 0),
+        };
+        digits.push(digit);
+        value = value.div_euclid(5) + carry;
+    }
+    digits.iter().rev().collect()
+}
+
+/// `scale` SNAFU numbers.
+pub fn day25(rng: &mut impl Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let magnitude = 10_i64.pow(rng.gen_range(1..=15));
+            to_snafu(rng.gen_range(1..=magnitude)) + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        for day in 1..=25 {
+            assert_eq!(
+                generate(day, &mut rng_from_seed(7), 10),
+                generate(day, &mut rng_from_seed(7), 10)
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_snafu() {
+        assert_eq!(to_snafu(1), "1");
+        assert_eq!(to_snafu(3), "1=");
+        assert_eq!(to_snafu(2022), "1=11-2");
+        assert_eq!(to_snafu(314159265), "1121-1110-1=0");
+    }
+}
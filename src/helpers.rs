@@ -1,4 +1,34 @@
 /*
  * Use this file if you want to extract helpers from your solutions.
- * Example import from this file: `use advent_of_code::helpers::example_fn;`.
+ * Example import from this file: `use advent_of_code::helpers::Grid;`.
  */
+pub mod balanced;
+pub mod bitset;
+pub mod checked;
+pub mod cycle;
+pub mod dir;
+pub mod extract;
+pub mod graph;
+pub mod grid;
+pub mod indexed_list;
+pub mod interner;
+pub mod interval;
+pub mod memo;
+pub mod num;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod search;
+pub mod sparse_grid;
+pub mod voxel;
+
+pub use balanced::{BalancedNumber, Snafu};
+pub use bitset::{BitSet, GrowableBitSet};
+pub use dir::{Dir4, Dir8};
+pub use extract::ints;
+pub use grid::Grid;
+pub use indexed_list::IndexedList;
+pub use interner::Interner;
+pub use interval::{Interval, IntervalSet};
+pub use point::{Point2, Point3};
+pub use sparse_grid::SparseGrid;
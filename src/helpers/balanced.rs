@@ -0,0 +1,365 @@
+/*
+ * Integers written in a balanced base: an odd `BASE` whose digits go from -BASE/2 to BASE/2,
+ * such as SNAFU (base 5, digits `=-012`) or balanced ternary (digits `-01`).
+ * Numbers can have any number of digits, arithmetic works on the digits directly.
+ * Only SNAFU has a default alphabet for `FromStr` and `Display`, other bases write their
+ * digits with the symbols passed to `parse_with` and `to_string_with`, lowest digit first.
+ * example: `let total: Snafu = lines.map(|l| l.parse::<Snafu>().unwrap()).sum();`
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+pub type Snafu = BalancedNumber<5>;
+
+// the SNAFU digits from -2 to 2.
+const SNAFU_SYMBOLS: &str = "=-012";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseBalancedError {
+    Empty,
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseBalancedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBalancedError::Empty => write!(f, "cannot parse a number without digits"),
+            ParseBalancedError::InvalidDigit(c) => write!(f, "invalid digit {:?}", c),
+        }
+    }
+}
+
+impl std::error::Error for ParseBalancedError {}
+
+/// The number does not fit in an `i128`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TryFromBalancedError;
+
+impl fmt::Display for TryFromBalancedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "number out of range for i128")
+    }
+}
+
+impl std::error::Error for TryFromBalancedError {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BalancedNumber<const BASE: u32> {
+    // least significant first, without trailing zeros: zero has no digits at all.
+    digits: Vec<i8>,
+}
+
+impl<const BASE: u32> BalancedNumber<BASE> {
+    const HALF: i64 = {
+        assert!(
+            BASE % 2 == 1 && BASE <= 127,
+            "balanced bases are odd and fit in i8"
+        );
+        BASE as i64 / 2
+    };
+
+    pub fn zero() -> Self {
+        BalancedNumber { digits: vec![] }
+    }
+
+    // digits of any size, least significant first, carried into digits of the base.
+    fn from_sums(sums: impl IntoIterator<Item = i64>) -> Self {
+        let base = BASE as i64;
+        let mut digits = vec![];
+        let mut carry = 0;
+
+        let mut sums = sums.into_iter();
+        loop {
+            let sum = match sums.next() {
+                Some(sum) => sum + carry,
+                None if carry != 0 => carry,
+                None => break,
+            };
+            let digit = (sum + Self::HALF).rem_euclid(base) - Self::HALF;
+            digits.push(digit as i8);
+            carry = (sum - digit) / base;
+        }
+
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        BalancedNumber { digits }
+    }
+
+    /// -1, 0 or 1, like the sign of its most significant digit.
+    pub fn signum(&self) -> i8 {
+        self.digits.last().map_or(0, |digit| digit.signum())
+    }
+
+    /// The digits from the most significant one, a single 0 for zero.
+    pub fn digits(&self) -> Vec<i8> {
+        if self.digits.is_empty() {
+            return vec![0];
+        }
+        self.digits.iter().rev().copied().collect()
+    }
+
+    /// Parses digits written with `symbols`, one per digit from the lowest to the highest,
+    /// e.g. `=-012` for SNAFU.
+    pub fn parse_with(s: &str, symbols: &str) -> Result<Self, ParseBalancedError> {
+        let symbols: Vec<char> = symbols.chars().collect();
+        assert_eq!(symbols.len(), BASE as usize, "one symbol per digit");
+        if s.is_empty() {
+            return Err(ParseBalancedError::Empty);
+        }
+
+        let sums = s
+            .chars()
+            .rev()
+            .map(|c| match symbols.iter().position(|symbol| *symbol == c) {
+                Some(index) => Ok(index as i64 - Self::HALF),
+                None => Err(ParseBalancedError::InvalidDigit(c)),
+            })
+            .collect::<Result<Vec<i64>, _>>()?;
+        Ok(Self::from_sums(sums))
+    }
+
+    /// The digits written with `symbols`, as for `parse_with`.
+    pub fn to_string_with(&self, symbols: &str) -> String {
+        let symbols: Vec<char> = symbols.chars().collect();
+        assert_eq!(symbols.len(), BASE as usize, "one symbol per digit");
+        self.digits()
+            .iter()
+            .map(|digit| symbols[(*digit as i64 + Self::HALF) as usize])
+            .collect()
+    }
+}
+
+impl<const BASE: u32> From<i128> for BalancedNumber<BASE> {
+    fn from(mut value: i128) -> Self {
+        let (base, half) = (BASE as i128, Self::HALF as i128);
+        let mut digits = vec![];
+
+        while value != 0 {
+            // rounding the quotient up for the high remainders, which never overflows.
+            let remainder = value.rem_euclid(base);
+            let (digit, round_up) = if remainder > half {
+                (remainder - base, 1)
+            } else {
+                (remainder, 0)
+            };
+            digits.push(digit as i8);
+            value = value.div_euclid(base) + round_up;
+        }
+
+        BalancedNumber { digits }
+    }
+}
+
+impl<const BASE: u32> TryFrom<BalancedNumber<BASE>> for i128 {
+    type Error = TryFromBalancedError;
+
+    fn try_from(number: BalancedNumber<BASE>) -> Result<Self, Self::Error> {
+        i128::try_from(&number)
+    }
+}
+
+impl<const BASE: u32> TryFrom<&BalancedNumber<BASE>> for i128 {
+    type Error = TryFromBalancedError;
+
+    fn try_from(number: &BalancedNumber<BASE>) -> Result<Self, Self::Error> {
+        let base = BASE as i128;
+        number.digits.iter().rev().try_fold(0i128, |acc, digit| {
+            let (digit, sign) = (*digit as i128, digit.signum() as i128);
+            acc.checked_mul(base)
+                .and_then(|acc| acc.checked_add(digit))
+                // `acc * base` alone may not fit, as for i128::MIN, but the digit brings it back.
+                .or_else(|| {
+                    (acc.checked_add(sign)?)
+                        .checked_mul(base)?
+                        .checked_add(digit - sign * base)
+                })
+                .ok_or(TryFromBalancedError)
+        })
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseBalancedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, SNAFU_SYMBOLS)
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(SNAFU_SYMBOLS))
+    }
+}
+
+impl<const BASE: u32> Add for BalancedNumber<BASE> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        &self + &other
+    }
+}
+
+impl<const BASE: u32> Add for &BalancedNumber<BASE> {
+    type Output = BalancedNumber<BASE>;
+
+    fn add(self, other: Self) -> BalancedNumber<BASE> {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |number: &BalancedNumber<BASE>, i| *number.digits.get(i).unwrap_or(&0) as i64;
+        BalancedNumber::from_sums((0..len).map(|i| digit(self, i) + digit(other, i)))
+    }
+}
+
+impl<const BASE: u32> Neg for BalancedNumber<BASE> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        BalancedNumber {
+            digits: self.digits.iter().map(|digit| -digit).collect(),
+        }
+    }
+}
+
+impl<const BASE: u32> Sub for BalancedNumber<BASE> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const BASE: u32> Mul for BalancedNumber<BASE> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        &self * &other
+    }
+}
+
+impl<const BASE: u32> Mul for &BalancedNumber<BASE> {
+    type Output = BalancedNumber<BASE>;
+
+    fn mul(self, other: Self) -> BalancedNumber<BASE> {
+        let len = (self.digits.len() + other.digits.len()).saturating_sub(1);
+        let mut sums = vec![0i64; len];
+        for (i, a) in self.digits.iter().enumerate() {
+            for (j, b) in other.digits.iter().enumerate() {
+                sums[i + j] += *a as i64 * *b as i64;
+            }
+        }
+        BalancedNumber::from_sums(sums)
+    }
+}
+
+impl<const BASE: u32> Sum for BalancedNumber<BASE> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BalancedNumber::zero(), |acc, number| acc + number)
+    }
+}
+
+// a longer number is further from zero, and digits compare in order otherwise.
+impl<const BASE: u32> Ord for BalancedNumber<BASE> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let sign = self.signum();
+        sign.cmp(&other.signum())
+            .then_with(|| {
+                let len = |number: &Self| sign as i64 * number.digits.len() as i64;
+                len(self).cmp(&len(other))
+            })
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl<const BASE: u32> PartialOrd for BalancedNumber<BASE> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snafu(value: i128) -> Snafu {
+        Snafu::from(value)
+    }
+
+    #[test]
+    fn test_snafu() {
+        for (value, digits) in [
+            (0, "0"),
+            (1, "1"),
+            (3, "1="),
+            (4, "1-"),
+            (10, "20"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+            (-8, "=2"),
+        ] {
+            assert_eq!(snafu(value).to_string(), digits);
+            assert_eq!(digits.parse(), Ok(snafu(value)));
+        }
+        assert_eq!("002".parse(), Ok(snafu(2)));
+        assert_eq!(snafu(7).digits(), [1, 2]);
+        assert_eq!("".parse::<Snafu>(), Err(ParseBalancedError::Empty));
+        assert_eq!(
+            "13".parse::<Snafu>(),
+            Err(ParseBalancedError::InvalidDigit('3'))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values: Vec<i128> = (-200..=200)
+            .chain([i128::MAX, i128::MIN, 1 << 100])
+            .collect();
+        for a in values.iter().copied() {
+            assert_eq!(i128::try_from(snafu(a)), Ok(a));
+            assert_eq!(snafu(a).to_string().parse(), Ok(snafu(a)));
+            if let Some(negated) = a.checked_neg() {
+                assert_eq!(-snafu(a), snafu(negated));
+            }
+        }
+
+        for a in (-60..=60).step_by(7) {
+            for b in -60..=60 {
+                assert_eq!(snafu(a) + snafu(b), snafu(a + b));
+                assert_eq!(snafu(a) - snafu(b), snafu(a - b));
+                assert_eq!(snafu(a) * snafu(b), snafu(a * b));
+                assert_eq!(snafu(a).cmp(&snafu(b)), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let big = snafu(i128::MAX) * snafu(i128::MAX);
+        assert!(big > snafu(i128::MAX));
+        assert_eq!(i128::try_from(&big), Err(TryFromBalancedError));
+        assert_eq!(big - snafu(i128::MAX) * snafu(i128::MAX), Snafu::zero());
+        assert_eq!(i128::try_from(snafu(i128::MAX) + snafu(i128::MIN)), Ok(-1));
+    }
+
+    #[test]
+    fn test_other_bases() {
+        let ternary = BalancedNumber::<3>::from(8);
+        assert_eq!(ternary.to_string_with("-01"), "10-");
+        assert_eq!(ternary.to_string_with("T01"), "10T");
+        assert_eq!(BalancedNumber::parse_with("10T", "T01"), Ok(ternary));
+
+        // digits from -5 to 5.
+        let symbols = "edcba012345";
+        let big = BalancedNumber::<11>::from(-1234);
+        assert_eq!(big.to_string_with(symbols), "a1bb");
+        assert_eq!(
+            BalancedNumber::parse_with(&big.to_string_with(symbols), symbols),
+            Ok(big.clone())
+        );
+        assert_eq!(i128::try_from(big), Ok(-1234));
+    }
+}
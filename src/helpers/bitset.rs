@@ -0,0 +1,433 @@
+/*
+ * Sets of small integers stored as bits.
+ * `BitSet` fits in a single word (`u64` by default, up to `u128`) and is `Copy`,
+ * which makes it cheap to carry around in search states.
+ * `GrowableBitSet` has no upper bound, and grows a word at a time.
+ */
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Sub};
+
+/// Unsigned integers used as the storage of a `BitSet`.
+pub trait Word:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + Default
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<usize, Output = Self>
+{
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64, u128);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<W = u64> {
+    bits: W,
+}
+
+impl<W: Word> BitSet<W> {
+    /// The largest element plus one.
+    pub const CAPACITY: usize = W::BITS;
+
+    pub fn new() -> Self {
+        BitSet { bits: W::ZERO }
+    }
+
+    pub fn from_bits(bits: W) -> Self {
+        BitSet { bits }
+    }
+
+    pub fn bits(self) -> W {
+        self.bits
+    }
+
+    fn mask(value: usize) -> W {
+        assert!(
+            value < W::BITS,
+            "{} does not fit in a set of {} bits",
+            value,
+            W::BITS
+        );
+        W::ONE << value
+    }
+
+    /// Returns whether `value` was missing, panics if it is out of capacity.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let before = self.bits;
+        self.bits = self.bits | Self::mask(value);
+        before != self.bits
+    }
+
+    /// Returns whether `value` was there.
+    pub fn remove(&mut self, value: usize) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+        self.bits = self.bits & !Self::mask(value);
+        true
+    }
+
+    /// A copy of the set with `value` added.
+    pub fn with(mut self, value: usize) -> Self {
+        self.insert(value);
+        self
+    }
+
+    pub fn contains(self, value: usize) -> bool {
+        value < W::BITS && self.bits & (W::ONE << value) != W::ZERO
+    }
+
+    pub fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.bits == W::ZERO
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        BitSet::from_bits(self.bits | other.bits)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        BitSet::from_bits(self.bits & other.bits)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        BitSet::from_bits(self.bits & !other.bits)
+    }
+
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        BitSet::from_bits(self.bits ^ other.bits)
+    }
+
+    pub fn is_subset(self, other: Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_disjoint(self, other: Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    /// The elements in increasing order.
+    pub fn iter(self) -> Iter<W> {
+        Iter { bits: self.bits }
+    }
+}
+
+pub struct Iter<W> {
+    bits: W,
+}
+
+impl<W: Word> Iterator for Iter<W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.bits == W::ZERO {
+            return None;
+        }
+        let value = self.bits.trailing_zeros() as usize;
+        self.bits = self.bits ^ (W::ONE << value);
+        Some(value)
+    }
+}
+
+impl<W: Word> IntoIterator for BitSet<W> {
+    type Item = usize;
+    type IntoIter = Iter<W>;
+
+    fn into_iter(self) -> Iter<W> {
+        self.iter()
+    }
+}
+
+impl<W: Word> FromIterator<usize> for BitSet<W> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut result = BitSet::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<W: Word> Extend<usize> for BitSet<W> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<W: Word> BitOr for BitSet<W> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl<W: Word> BitAnd for BitSet<W> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl<W: Word> BitXor for BitSet<W> {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        self.symmetric_difference(other)
+    }
+}
+
+impl<W: Word> Sub for BitSet<W> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+/// Prints the elements, as for a `HashSet`.
+impl<W: Word> fmt::Debug for BitSet<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// A bit set without an upper bound.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct GrowableBitSet {
+    // never ends with an empty word, so that equal sets are equal words.
+    words: Vec<u64>,
+}
+
+impl GrowableBitSet {
+    pub fn new() -> Self {
+        GrowableBitSet { words: vec![] }
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    fn word(&self, index: usize) -> BitSet<u64> {
+        BitSet::from_bits(self.words.get(index).copied().unwrap_or(0))
+    }
+
+    fn from_words(words: Vec<u64>) -> Self {
+        let mut result = GrowableBitSet { words };
+        result.trim();
+        result
+    }
+
+    /// Returns whether `value` was missing.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (index, bit) = (value / 64, value % 64);
+        if index >= self.words.len() {
+            self.words.resize(index + 1, 0);
+        }
+        let mut word = self.word(index);
+        let inserted = word.insert(bit);
+        self.words[index] = word.bits();
+        inserted
+    }
+
+    /// Returns whether `value` was there.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (index, bit) = (value / 64, value % 64);
+        let mut word = self.word(index);
+        if !word.remove(bit) {
+            return false;
+        }
+        self.words[index] = word.bits();
+        self.trim();
+        true
+    }
+
+    /// A copy of the set with `value` added.
+    pub fn with(&self, value: usize) -> Self {
+        let mut result = self.clone();
+        result.insert(value);
+        result
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.word(value / 64).contains(value % 64)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    // combines the words of both sets, the shorter one padded with zeros.
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.words.len().max(other.words.len());
+        let words = (0..len)
+            .map(|i| f(self.word(i).bits(), other.word(i).bits()))
+            .collect();
+        GrowableBitSet::from_words(words)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        (0..self.words.len()).all(|i| self.words[i] & !other.word(i).bits() == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .all(|(a, b)| a & b == 0)
+    }
+
+    /// The elements in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, w)| BitSet::from_bits(*w).iter().map(move |bit| i * 64 + bit))
+    }
+}
+
+impl FromIterator<usize> for GrowableBitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut result = GrowableBitSet::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl Extend<usize> for GrowableBitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl fmt::Debug for GrowableBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        let mut set: BitSet = BitSet::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(63));
+        assert!(set.contains(3) && set.contains(63));
+        assert!(!set.contains(4) && !set.contains(64));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![63]);
+        assert_eq!(format!("{:?}", set.with(1)), "{1, 63}");
+
+        let big = BitSet::<u128>::from_iter([0, 100, 127]);
+        assert_eq!(big.iter().collect::<Vec<_>>(), vec![0, 100, 127]);
+        assert_eq!(BitSet::<u8>::CAPACITY, 8);
+    }
+
+    #[test]
+    fn test_bitset_algebra() {
+        let a: BitSet<u16> = [1, 2, 3].into_iter().collect();
+        let b: BitSet<u16> = [3, 4].into_iter().collect();
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![3]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![1, 2, 4]);
+        assert!((a & b).is_subset(a) && !a.is_subset(b));
+        assert!((a - b).is_disjoint(b));
+        assert_eq!(a.union(b), b | a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bitset_out_of_capacity() {
+        BitSet::<u32>::new().insert(32);
+    }
+
+    #[test]
+    fn test_growable() {
+        let mut set = GrowableBitSet::new();
+        assert!(set.insert(200));
+        assert!(set.insert(5));
+        assert!(!set.insert(200));
+        assert!(set.contains(200) && !set.contains(1000));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![5, 200]);
+
+        // removing the highest element gives back the same set as never inserting it.
+        set.remove(200);
+        assert_eq!(set, GrowableBitSet::from_iter([5]));
+
+        let a = GrowableBitSet::from_iter([1, 70, 130]);
+        let b = GrowableBitSet::from_iter([70, 300]);
+        assert_eq!(a.intersection(&b), GrowableBitSet::from_iter([70]));
+        assert_eq!(a.union(&b).len(), 4);
+        assert_eq!(a.difference(&b), GrowableBitSet::from_iter([1, 130]));
+        assert_eq!(a.symmetric_difference(&b).len(), 3);
+        assert!(a.difference(&a).is_empty());
+        assert!(!a.is_disjoint(&b) && a.intersection(&b).is_subset(&b));
+    }
+}
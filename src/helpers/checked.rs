@@ -0,0 +1,190 @@
+/*
+ * Integers whose arithmetic checks for overflow even in release builds. The first operation
+ * that overflows is kept, with its operands, the type and the line it was on, and carried
+ * through the rest of the computation instead of a wrong value.
+ * A solver can then return the `Overflow` and be run again over a wider type.
+ * example: `solve::<i64>(&numbers).map(i128::from).or_else(|_| solve::<i128>(&numbers))`
+ */
+use super::num::Integer;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
+use std::panic::Location;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checked<T>(Result<T, Overflow<T>>);
+
+/// An operation whose result does not fit in `T`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overflow<T> {
+    pub lhs: T,
+    pub symbol: &'static str,
+    pub rhs: T,
+    pub location: &'static Location<'static>,
+}
+
+impl<T: Integer> fmt::Display for Overflow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} overflows {} at {}",
+            self.lhs,
+            self.symbol,
+            self.rhs,
+            std::any::type_name::<T>(),
+            self.location
+        )
+    }
+}
+
+impl<T: Integer> std::error::Error for Overflow<T> {}
+
+impl<T: Integer> Checked<T> {
+    pub fn new(value: T) -> Self {
+        Checked(Ok(value))
+    }
+
+    /// The value, or the first operation that overflowed on the way to it.
+    pub fn get(self) -> Result<T, Overflow<T>> {
+        self.0
+    }
+}
+
+// dividing by zero is a bug rather than a number too large for `T`, so it still panics.
+#[track_caller]
+fn overflow<T: Integer>(lhs: T, symbol: &'static str, rhs: T) -> Overflow<T> {
+    if rhs == T::ZERO && (symbol == "/" || symbol == "%") {
+        panic!("{} {} 0 divides by zero", lhs, symbol);
+    }
+    Overflow {
+        lhs,
+        symbol,
+        rhs,
+        location: Location::caller(),
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $checked:ident,
+     $symbol:literal) => {
+        impl<T: Integer> $op for Checked<T> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, other: Self) -> Self {
+                let (lhs, rhs) = match (self.0, other.0) {
+                    (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+                    (Err(first), _) | (_, Err(first)) => return Checked(Err(first)),
+                };
+                match lhs.$checked(rhs) {
+                    Some(result) => Checked(Ok(result)),
+                    None => Checked(Err(overflow(lhs, $symbol, rhs))),
+                }
+            }
+        }
+
+        impl<T: Integer> $op<T> for Checked<T> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, other: T) -> Self {
+                $op::$method(self, Checked::new(other))
+            }
+        }
+
+        impl<T: Integer> $op_assign for Checked<T> {
+            #[track_caller]
+            fn $method_assign(&mut self, other: Self) {
+                *self = $op::$method(*self, other);
+            }
+        }
+
+        impl<T: Integer> $op_assign<T> for Checked<T> {
+            #[track_caller]
+            fn $method_assign(&mut self, other: T) {
+                *self = $op::$method(*self, Checked::new(other));
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, AddAssign, add_assign, checked_add, "+");
+impl_op!(Sub, sub, SubAssign, sub_assign, checked_sub, "-");
+impl_op!(Mul, mul, MulAssign, mul_assign, checked_mul, "*");
+impl_op!(Div, div, DivAssign, div_assign, checked_div, "/");
+impl_op!(Rem, rem, RemAssign, rem_assign, checked_rem, "%");
+
+impl<T: Integer> Sum for Checked<T> {
+    #[track_caller]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut result = Checked::new(T::ZERO);
+        for value in iter {
+            result += value;
+        }
+        result
+    }
+}
+
+impl<T: Integer> From<T> for Checked<T> {
+    fn from(value: T) -> Self {
+        Checked::new(value)
+    }
+}
+
+impl<T: Integer> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Ok(value) => fmt::Display::fmt(value, f),
+            Err(overflow) => overflow.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked() {
+        let mut value = Checked::new(200u8);
+        value += 50;
+        assert_eq!(value.get(), Ok(250));
+        assert_eq!((value / 7 % Checked::new(4)).get(), Ok(3));
+        assert_eq!((Checked::new(-3i64) * Checked::new(4) - 1).get(), Ok(-13));
+        assert_eq!(
+            [1u32, 2, 3]
+                .into_iter()
+                .map(Checked::new)
+                .sum::<Checked<u32>>(),
+            Checked::new(6)
+        );
+        assert_eq!(Checked::new(7).to_string(), "7");
+    }
+
+    #[test]
+    fn test_overflow() {
+        // the first overflow is kept, whatever comes after it.
+        let result = (Checked::new(250u8) + 10) - 100 + 1;
+        let overflow = result.get().unwrap_err();
+        assert_eq!(
+            (overflow.lhs, overflow.symbol, overflow.rhs),
+            (250, "+", 10)
+        );
+        assert!(result
+            .to_string()
+            .starts_with("250 + 10 overflows u8 at src/helpers/checked.rs"));
+
+        // a solver and its fallback over a wider type.
+        let square = |n: u64| (Checked::new(n) * n).get();
+        let wide_square = |n: u64| (Checked::new(n as u128) * n as u128).get();
+        let solve = |n| square(n).map(u128::from).or_else(|_| wide_square(n));
+        assert_eq!(solve(3), Ok(9));
+        assert_eq!(solve(1 << 40), Ok(1 << 80));
+    }
+
+    #[test]
+    #[should_panic(expected = "1 / 0 divides by zero")]
+    fn test_divide_by_zero() {
+        let _ = Checked::new(1) / 0;
+    }
+}
@@ -0,0 +1,183 @@
+/*
+ * Cycle detection for simulations that eventually repeat themselves,
+ * and extrapolation of a metric (a height, a score...) to a step far in the future.
+ */
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// States repeat from step `start` on, every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The metric after `n` steps, given its values for the first `start + length` steps at least.
+    /// The metric is expected to grow by the same amount on every cycle.
+    pub fn extrapolate(&self, history: &[i64], n: usize) -> i64 {
+        if n < history.len() {
+            return history[n];
+        }
+
+        let per_cycle = history[self.start + self.length] - history[self.start];
+        let cycles = (n - self.start) / self.length;
+        let offset = (n - self.start) % self.length;
+        history[self.start + offset] + cycles as i64 * per_cycle
+    }
+}
+
+/// Steps `state` until its `fingerprint` repeats, giving up after `limit` steps.
+/// Two states with the same fingerprint must behave the same from then on.
+pub fn find_cycle<S, K: Eq + Hash>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut fingerprint: impl FnMut(&S) -> K,
+    limit: usize,
+) -> Option<Cycle> {
+    let mut seen: FxHashMap<K, usize> = FxHashMap::default();
+    seen.insert(fingerprint(state), 0);
+
+    for i in 1..=limit {
+        step(state);
+        if let Some(start) = seen.insert(fingerprint(state), i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+    }
+
+    None
+}
+
+/// The value of `metric` after `n` steps, skipping over whole cycles once one is found.
+/// `None` if no cycle shows up within `budget` steps and `n` is further away than that.
+pub fn extrapolate<S, K: Eq + Hash>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    fingerprint: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> i64,
+    n: usize,
+    budget: usize,
+) -> Option<i64> {
+    let mut history = vec![metric(&state)];
+    let cycle = find_cycle(
+        &mut state,
+        |s| {
+            step(s);
+            history.push(metric(s));
+        },
+        fingerprint,
+        n.min(budget),
+    );
+
+    match cycle {
+        Some(cycle) => Some(cycle.extrapolate(&history, n)),
+        None => history.get(n).copied(),
+    }
+}
+
+/// Brent's algorithm, for sequences `x, f(x), f(f(x))...` of comparable values.
+/// Only keeps two values around, at the cost of calling `f` a few more times.
+pub fn brent<T: Clone + Eq>(initial: T, f: impl Fn(&T) -> T) -> Cycle {
+    // find the length with a tortoise teleporting to the hare at every power of two.
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // then the start, moving both one length apart until they meet.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 3 -> 10 -> 101 -> 2 -> 5 -> 26 -> 167 -> 95 -> 101
+        let mut state = 3;
+        let cycle = find_cycle(&mut state, |x| *x = next(x), |x| *x, 100);
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 2,
+                length: 6
+            })
+        );
+        assert_eq!(state, 101);
+
+        let mut counter = 0;
+        assert_eq!(find_cycle(&mut counter, |x| *x += 1, |x| *x, 100), None);
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(
+            brent(3, next),
+            Cycle {
+                start: 2,
+                length: 6
+            }
+        );
+        assert_eq!(
+            brent(0, |x| (x + 1) % 5),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // a position on a ring of 4 cells, with a score of 10 per lap.
+        let step = |(pos, laps): &mut (u32, i64)| {
+            *pos = (*pos + 1) % 4;
+            if *pos == 0 {
+                *laps += 1;
+            }
+        };
+        let score = |(pos, laps): &(u32, i64)| *laps * 10 + *pos as i64;
+
+        for n in [0, 3, 4, 9, 1_000_000_000_001] {
+            let expected = (n / 4) as i64 * 10 + (n % 4) as i64;
+            let result = extrapolate((0, 0), step, |s| s.0, score, n, 100);
+            assert_eq!(result, Some(expected));
+        }
+
+        // without a cycle, only the steps within the budget are known.
+        let count = |x: &i64| *x;
+        assert_eq!(extrapolate(0, |x| *x += 1, count, count, 50, 100), Some(50));
+        assert_eq!(
+            extrapolate(0, |x| *x += 1, count, count, 1 << 40, 100),
+            None
+        );
+    }
+}
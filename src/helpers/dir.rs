@@ -0,0 +1,269 @@
+/*
+ * The four orthogonal and eight compass directions.
+ * Steps follow the screen convention of the grids: x grows to the right and y grows down,
+ * so turning right is turning clockwise.
+ */
+use super::Point2;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirError(String);
+
+impl fmt::Display for ParseDirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown direction \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirError {}
+
+impl Dir4 {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Self::ALL.into_iter()
+    }
+
+    // position in `ALL`.
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn step(self) -> Point2<i32> {
+        Dir8::from(self).step()
+    }
+
+    /// The step for maps where y grows upwards.
+    pub fn step_y_up(self) -> Point2<i32> {
+        let step = self.step();
+        Point2::new(step.x, -step.y)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Dir4::Left | Dir4::Right)
+    }
+
+    /// The direction along with its two diagonals, e.g. `UpLeft, Up, UpRight` for `Up`.
+    pub fn cone(self) -> [Dir8; 3] {
+        let dir = Dir8::from(self);
+        [dir.turn_left(), dir, dir.turn_right()]
+    }
+
+    /// Parses a letter (`U`, `R`, `N`, `E`, ...) or an arrow (`^`, `>`, `v`, `<`).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'U' | 'N' | '^' => Some(Dir4::Up),
+            'R' | 'E' | '>' => Some(Dir4::Right),
+            'D' | 'S' | 'V' => Some(Dir4::Down),
+            'L' | 'W' | '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+/// Accepts single letters and arrows (see `from_char`), or names such as `up` or `north`.
+impl FromStr for Dir4 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Dir4::from_char(c).ok_or_else(|| ParseDirError(s.to_string()));
+        }
+
+        match s.to_ascii_lowercase().as_str() {
+            "up" | "north" => Ok(Dir4::Up),
+            "right" | "east" => Ok(Dir4::Right),
+            "down" | "south" => Ok(Dir4::Down),
+            "left" | "west" => Ok(Dir4::Left),
+            _ => Err(ParseDirError(s.to_string())),
+        }
+    }
+}
+
+/// Prints the arrow.
+impl fmt::Display for Dir4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl Dir8 {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Self::ALL.into_iter()
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn step(self) -> Point2<i32> {
+        let (x, y) = match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        };
+        Point2::new(x, y)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir.index() * 2]
+    }
+}
+
+/// Accepts `Dir4` names and compass points such as `NE` or `north-east`.
+impl FromStr for Dir8 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(dir) = s.parse::<Dir4>() {
+            return Ok(dir.into());
+        }
+
+        let name = s.to_ascii_lowercase().replace(['-', '_', ' '], "");
+        match name.as_str() {
+            "ne" | "northeast" | "upright" => Ok(Dir8::UpRight),
+            "se" | "southeast" | "downright" => Ok(Dir8::DownRight),
+            "sw" | "southwest" | "downleft" => Ok(Dir8::DownLeft),
+            "nw" | "northwest" | "upleft" => Ok(Dir8::UpLeft),
+            _ => Err(ParseDirError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.turn_around(), Dir4::Right);
+        assert!(Dir4::iter().all(|d| d.turn_left().turn_right() == d));
+
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::DownRight.turn_around(), Dir8::UpLeft);
+    }
+
+    #[test]
+    fn test_steps() {
+        assert_eq!(Dir4::Up.step(), Point2::new(0, -1));
+        assert_eq!(Dir4::Up.step_y_up(), Point2::new(0, 1));
+        assert_eq!(Dir4::Right.step(), Point2::new(1, 0));
+        // turning the direction turns its step the same way.
+        for dir in Dir4::iter() {
+            assert_eq!(dir.turn_right().step(), dir.step().rotate_left());
+        }
+
+        let total = Dir8::iter().fold(Point2::default(), |acc, d| acc + d.step());
+        assert_eq!(total, Point2::new(0, 0));
+        assert_eq!(Dir8::iter().filter(|d| d.is_diagonal()).count(), 4);
+    }
+
+    #[test]
+    fn test_cone() {
+        assert_eq!(
+            Dir4::Left.cone(),
+            [Dir8::DownLeft, Dir8::Left, Dir8::UpLeft]
+        );
+        assert!(Dir4::Down.cone().iter().all(|d| d.step().y == 1));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("R".parse(), Ok(Dir4::Right));
+        assert_eq!("n".parse(), Ok(Dir4::Up));
+        assert_eq!("v".parse(), Ok(Dir4::Down));
+        assert_eq!("West".parse(), Ok(Dir4::Left));
+        assert!("x".parse::<Dir4>().is_err());
+        assert!("NE".parse::<Dir4>().is_err());
+
+        assert_eq!("NE".parse(), Ok(Dir8::UpRight));
+        assert_eq!("south-west".parse(), Ok(Dir8::DownLeft));
+        assert_eq!("<".parse(), Ok(Dir8::Left));
+
+        assert_eq!(Dir4::Down.to_string(), "v");
+        assert!(Dir4::iter().all(|d| d.to_string().parse() == Ok(d)));
+    }
+}
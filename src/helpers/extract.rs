@@ -0,0 +1,170 @@
+/*
+ * Pulls every integer out of a line of free text, ignoring whatever surrounds them.
+ * A `-` right before the digits is a sign, unless it follows a letter or a digit as in `2-4`.
+ * example: `let [sx, sy, bx, by]: [i32; 4] = ints(line)?;`
+ */
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IntsError {
+    /// The line holds `found` integers where `expected` were wanted.
+    Count { expected: usize, found: usize },
+    /// An integer that does not fit the wanted type, e.g. a negative one for `u32`.
+    Invalid(String),
+}
+
+impl fmt::Display for IntsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntsError::Count { expected, found } => {
+                write!(f, "expected {} integers, found {}", expected, found)
+            }
+            IntsError::Invalid(number) => write!(f, "could not convert \"{}\"", number),
+        }
+    }
+}
+
+impl std::error::Error for IntsError {}
+
+/// Things that can be built from the integers of a line: a `Vec`, an array or a tuple.
+pub trait FromInts: Sized {
+    fn from_ints(numbers: &[&str]) -> Result<Self, IntsError>;
+}
+
+// the integers of `line`, signs included.
+fn find_ints(line: &str) -> Vec<&str> {
+    let bytes = line.as_bytes();
+    let mut result = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        if start > 0 && bytes[start - 1] == b'-' {
+            let before = start.checked_sub(2).map(|b| bytes[b]);
+            if !before.is_some_and(|b| b.is_ascii_alphanumeric()) {
+                start -= 1;
+            }
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        result.push(&line[start..i]);
+    }
+
+    result
+}
+
+fn convert<T: FromStr>(number: &str) -> Result<T, IntsError> {
+    number
+        .parse()
+        .map_err(|_| IntsError::Invalid(number.to_string()))
+}
+
+fn check_count(numbers: &[&str], expected: usize) -> Result<(), IntsError> {
+    if numbers.len() == expected {
+        Ok(())
+    } else {
+        Err(IntsError::Count {
+            expected,
+            found: numbers.len(),
+        })
+    }
+}
+
+/// Every integer of `line`, as many as there are.
+impl<T: FromStr> FromInts for Vec<T> {
+    fn from_ints(numbers: &[&str]) -> Result<Self, IntsError> {
+        numbers.iter().map(|n| convert(n)).collect()
+    }
+}
+
+/// Exactly `N` integers.
+impl<T: FromStr, const N: usize> FromInts for [T; N] {
+    fn from_ints(numbers: &[&str]) -> Result<Self, IntsError> {
+        check_count(numbers, N)?;
+        let values: Vec<T> = Vec::from_ints(numbers)?;
+        match values.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("the count was checked"),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($len:expr; $($t:ident),+) => {
+        /// Exactly as many integers as there are fields, each of its own type.
+        impl<$($t: FromStr),+> FromInts for ($($t,)+) {
+            fn from_ints(numbers: &[&str]) -> Result<Self, IntsError> {
+                check_count(numbers, $len)?;
+                let mut numbers = numbers.iter();
+                Ok(($(convert::<$t>(numbers.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(1; A);
+impl_tuple!(2; A, B);
+impl_tuple!(3; A, B, C);
+impl_tuple!(4; A, B, C, D);
+impl_tuple!(5; A, B, C, D, E);
+impl_tuple!(6; A, B, C, D, E, F);
+impl_tuple!(7; A, B, C, D, E, F, G);
+impl_tuple!(8; A, B, C, D, E, F, G, H);
+
+/// The integers of `line`, e.g. `ints::<(u32, i64)>("x: 3, y: -5")` or `ints::<Vec<u8>>(line)`.
+pub fn ints<T: FromInts>(line: &str) -> Result<T, IntsError> {
+    T::from_ints(&find_ints(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_ints() {
+        assert_eq!(
+            find_ints("Sensor at x=-2, y=15: beacon at x=10,y=-3"),
+            vec!["-2", "15", "10", "-3"]
+        );
+        // dashes between numbers or after words are separators.
+        assert_eq!(find_ints("2-4,-6--8"), vec!["2", "4", "-6", "-8"]);
+        assert_eq!(find_ints("valve-3 -> 4"), vec!["3", "4"]);
+        assert_eq!(find_ints("no numbers - here"), Vec::<&str>::new());
+        assert_eq!(find_ints("-"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = "Blueprint 1: costs 4 ore and 14 clay.";
+        assert_eq!(ints(line), Ok(vec![1_u32, 4, 14]));
+        assert_eq!(ints(line), Ok([1_u8, 4, 14]));
+        assert_eq!(ints(line), Ok((1_usize, 4_i64, 14_u16)));
+        assert_eq!(ints::<Vec<i32>>(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ints::<[i32; 2]>("1, 2, 3"),
+            Err(IntsError::Count {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            ints::<(u32, u32)>("x=1, y=-2"),
+            Err(IntsError::Invalid("-2".to_string()))
+        );
+        assert_eq!(
+            ints::<Vec<u8>>("1 256").unwrap_err().to_string(),
+            "could not convert \"256\""
+        );
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ * Small weighted graphs with labelled nodes, such as tunnels between named valves.
+ * Nodes get dense indices in order of appearance, edges are directed and weigh a number of steps.
+ * `contract` keeps only the interesting nodes, linked by the length of the shortest path
+ * between them: searches then jump from one interesting node to the next.
+ */
+use super::memo::Table;
+use super::Interner;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+#[derive(Clone, Debug, Default)]
+pub struct Graph<L> {
+    labels: Interner<L>,
+    // outgoing edges of every node, with their weight.
+    edges: Vec<Vec<(usize, u32)>>,
+}
+
+impl<L: Clone + Eq + Hash> Graph<L> {
+    pub fn new() -> Self {
+        Graph {
+            labels: Interner::new(),
+            edges: vec![],
+        }
+    }
+
+    /// A graph where every node leads to its neighbours in one step, e.g. from `(name, leads_to)`.
+    pub fn from_adjacency<N: IntoIterator<Item = L>>(
+        adjacency: impl IntoIterator<Item = (L, N)>,
+    ) -> Self {
+        let mut result = Graph::new();
+        for (from, neighbours) in adjacency {
+            result.add_node(from.clone());
+            for to in neighbours {
+                result.add_edge(from.clone(), to, 1);
+            }
+        }
+        result
+    }
+
+    /// The index of the node, added if it is new.
+    pub fn add_node(&mut self, label: L) -> usize {
+        let id = self.labels.intern(label);
+        if id == self.edges.len() {
+            self.edges.push(vec![]);
+        }
+        id
+    }
+
+    /// Adds a directed edge, replacing any previous edge between the same nodes.
+    pub fn add_edge(&mut self, from: L, to: L, weight: u32) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges[from].retain(|(next, _)| *next != to);
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn id(&self, label: &L) -> Option<usize> {
+        self.labels.get(label)
+    }
+
+    pub fn label(&self, id: usize) -> &L {
+        &self.labels[id]
+    }
+
+    /// The labels in order of their indices.
+    pub fn labels(&self) -> &[L] {
+        self.labels.values()
+    }
+
+    /// The nodes reachable from `id` in one edge, with the weight of that edge.
+    pub fn neighbours(&self, id: usize) -> &[(usize, u32)] {
+        &self.edges[id]
+    }
+
+    /// The length of the shortest path from `start` to every node, `None` if unreachable.
+    pub fn distances_from(&self, start: usize) -> Vec<Option<u32>> {
+        let mut result = vec![None; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((distance, node))) = heap.pop() {
+            if result[node].is_some() {
+                continue;
+            }
+            result[node] = Some(distance);
+
+            for (next, weight) in &self.edges[node] {
+                if result[*next].is_none() {
+                    heap.push(Reverse((distance + weight, *next)));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Shortest path lengths between every pair of nodes, indexed by `(from, to)`.
+    pub fn all_pairs(&self) -> Table<(usize, usize), Option<u32>> {
+        let mut result = Table::new((self.len(), self.len()), None);
+        for from in 0..self.len() {
+            for (to, distance) in self.distances_from(from).into_iter().enumerate() {
+                result[(from, to)] = distance;
+            }
+        }
+        result
+    }
+
+    /// The nodes accepted by `keep`, in the same order, each one linked to the others it can
+    /// reach by the length of the shortest path, whichever nodes that path goes through.
+    pub fn contract(&self, mut keep: impl FnMut(&L) -> bool) -> Self {
+        let kept: Vec<usize> = (0..self.len()).filter(|id| keep(self.label(*id))).collect();
+
+        let mut result = Graph::new();
+        for from in kept.iter() {
+            result.add_node(self.label(*from).clone());
+        }
+        for from in kept.iter() {
+            let distances = self.distances_from(*from);
+            for to in kept.iter().filter(|to| *to != from) {
+                if let Some(distance) = distances[*to] {
+                    result.add_edge(self.label(*from).clone(), self.label(*to).clone(), distance);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -- b -- c -- d, with a shortcut a -> d costing 5, and a lone e.
+    fn example() -> Graph<&'static str> {
+        let mut graph = Graph::from_adjacency([
+            ("a", vec!["b"]),
+            ("b", vec!["a", "c"]),
+            ("c", vec!["b", "d"]),
+            ("d", vec!["c"]),
+            ("e", vec![]),
+        ]);
+        graph.add_edge("a", "d", 5);
+        graph
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = example();
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.label(3), &"d");
+        assert_eq!(graph.neighbours(0), &[(1, 1), (3, 5)]);
+        assert_eq!(
+            graph.distances_from(0),
+            vec![Some(0), Some(1), Some(2), Some(3), None]
+        );
+
+        let pairs = graph.all_pairs();
+        assert_eq!(pairs[(3, 0)], Some(3));
+        assert_eq!(pairs[(4, 4)], Some(0));
+        assert_eq!(pairs[(0, 4)], None);
+    }
+
+    #[test]
+    fn test_contract() {
+        let graph = example().contract(|label| ["a", "d", "e"].contains(label));
+        assert_eq!(graph.labels(), ["a", "d", "e"]);
+        assert_eq!(graph.neighbours(0), &[(1, 3)]);
+        assert_eq!(graph.neighbours(1), &[(0, 3)]);
+        assert!(graph.neighbours(2).is_empty());
+    }
+}
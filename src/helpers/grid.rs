@@ -0,0 +1,273 @@
+/*
+ * A dense 2D grid, stored row by row.
+ * Positions are `(x, y)` tuples, with `(0, 0)` in the top left corner.
+ */
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Pos = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` can't be split into rows of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "cells don't fill the last row");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Maps every character of a character map to a cell, shorter lines are padded with spaces.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        let lines: Vec<&str> = input.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            let len = line.chars().count();
+            cells.extend(line.chars().map(&mut cell));
+            cells.extend((len..width).map(|_| cell(' ')));
+        }
+
+        Grid::from_vec(width, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    /// `pos` moved by `(dx, dy)`, if that is still inside the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(next).then_some(next)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Orthogonal neighbours inside the grid: up, right, down, left.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |offset| self.step(pos, *offset))
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid, clockwise from the top left.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |offset| self.step(pos, *offset))
+    }
+
+    /// Position of the first cell matching `predicate`, row by row.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456", |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let ragged = Grid::parse("#.\n#\n", |c| c);
+        assert_eq!(ragged.to_string(), "#.\n# ");
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = digits();
+        assert_eq!(grid.get((0, 1)), Some(&4));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+
+        *grid.get_mut((1, 1)).unwrap() = 0;
+        grid[(0, 0)] = 9;
+        assert_eq!(grid.to_string(), "923\n406");
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(
+            grid.column(2).rev().copied().collect::<Vec<_>>(),
+            vec![6, 3]
+        );
+        assert_eq!(
+            grid.columns().map(|c| c.sum::<u32>()).collect::<Vec<_>>(),
+            vec![5, 7, 9]
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 3);
+        assert_eq!(
+            grid.neighbours8((1, 0)).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+        assert_eq!(grid.step((0, 0), (2, 1)), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_find_and_map() {
+        let grid = Grid::parse("Sab\ncdE", |c| c);
+        assert_eq!(grid.find(|c| *c == 'S'), Some((0, 0)));
+        assert_eq!(grid.find(|c| *c == 'E'), Some((2, 1)));
+        assert_eq!(grid.find(|c| *c == 'z'), None);
+
+        let upper = grid.map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "SAB\nCDE");
+        assert_eq!(
+            upper.iter().nth(4).map(|(pos, c)| (pos, *c)),
+            Some(((1, 1), 'D'))
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<char> = Grid::parse("", |c| c);
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+}
@@ -0,0 +1,345 @@
+/*
+ * A sequence with O(log n) insertion and removal at any index, such as a list being mixed.
+ * Every element gets a `Handle` that stays valid while it moves around, so that its current
+ * index can be looked up in O(log n) too.
+ * It is an implicit treap: a binary tree ordered by position and balanced by random priorities,
+ * where every node knows the size of its subtree and its parent.
+ */
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+
+/// Points to an element of the list that created it, wherever the element moves to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle(usize);
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    value: T,
+    priority: u64,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+    // the number of elements in the subtree rooted here.
+    size: usize,
+    in_list: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct IndexedList<T> {
+    // every node ever inserted, removed ones included, a handle being an index in there.
+    nodes: Vec<Node<T>>,
+    root: Option<usize>,
+    seed: u64,
+}
+
+impl<T> Default for IndexedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IndexedList<T> {
+    pub fn new() -> Self {
+        IndexedList {
+            nodes: vec![],
+            root: None,
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn push(&mut self, value: T) -> Handle {
+        self.insert(self.len(), value)
+    }
+
+    /// Inserts `value` so that it ends up at `index`, panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) -> Handle {
+        let priority = self.next_priority();
+        self.nodes.push(Node {
+            value,
+            priority,
+            left: None,
+            right: None,
+            parent: None,
+            size: 1,
+            in_list: false,
+        });
+        let handle = Handle(self.nodes.len() - 1);
+        self.attach(handle, index);
+        handle
+    }
+
+    /// Takes the element out of the list and returns the index it had, `None` if it was not in
+    /// the list. Its value stays readable through the handle, and `move_to` puts it back.
+    pub fn remove(&mut self, handle: Handle) -> Option<usize> {
+        let index = self.index_of(handle)?;
+        let (before, rest) = self.split(self.root, index);
+        let (_, after) = self.split(rest, 1);
+        self.root = self.merge(before, after);
+        self.set_root_parent();
+
+        let node = &mut self.nodes[handle.0];
+        (node.left, node.right, node.parent) = (None, None, None);
+        node.size = 1;
+        node.in_list = false;
+        Some(index)
+    }
+
+    /// Moves the element so that it ends up at `index`, counted as if it had been removed first.
+    /// Panics if that index is past the end.
+    pub fn move_to(&mut self, handle: Handle, index: usize) {
+        let others = self.len() - usize::from(self.nodes[handle.0].in_list);
+        assert!(index <= others, "index {} out of bounds", index);
+        self.remove(handle);
+        self.attach(handle, index);
+    }
+
+    /// The current index of the element, `None` if it was removed.
+    pub fn index_of(&self, handle: Handle) -> Option<usize> {
+        if !self.nodes[handle.0].in_list {
+            return None;
+        }
+
+        // every parent we reach from its right adds its left subtree and itself.
+        let mut node = handle.0;
+        let mut index = self.size(self.nodes[node].left);
+        while let Some(parent) = self.nodes[node].parent {
+            if self.nodes[parent].right == Some(node) {
+                index += self.size(self.nodes[parent].left) + 1;
+            }
+            node = parent;
+        }
+        Some(index)
+    }
+
+    /// The handle of the element at `index`.
+    pub fn handle_at(&self, index: usize) -> Option<Handle> {
+        let mut node = self.root?;
+        let mut index = index;
+        loop {
+            let left_size = self.size(self.nodes[node].left);
+            match index.cmp(&left_size) {
+                Ordering::Less => node = self.nodes[node].left?,
+                Ordering::Equal => return Some(Handle(node)),
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    node = self.nodes[node].right?;
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.handle_at(index).map(|handle| &self[handle])
+    }
+
+    /// The values in list order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            list: self,
+            stack: vec![],
+        };
+        iter.push_left(self.root);
+        iter
+    }
+
+    fn attach(&mut self, handle: Handle, index: usize) {
+        assert!(index <= self.len(), "index {} out of bounds", index);
+        let (before, after) = self.split(self.root, index);
+        self.nodes[handle.0].in_list = true;
+        let merged = self.merge(before, Some(handle.0));
+        self.root = self.merge(merged, after);
+        self.set_root_parent();
+    }
+
+    // xorshift, random enough to keep the tree balanced and the same on every run.
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |node| self.nodes[node].size)
+    }
+
+    // recomputes the size of `node` and adopts its children.
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(node);
+        }
+    }
+
+    fn set_root_parent(&mut self) {
+        if let Some(root) = self.root {
+            self.nodes[root].parent = None;
+        }
+    }
+
+    // the first `count` elements of the tree, and the others.
+    fn split(&mut self, node: Option<usize>, count: usize) -> (Option<usize>, Option<usize>) {
+        let Some(node) = node else {
+            return (None, None);
+        };
+        let left_size = self.size(self.nodes[node].left);
+
+        if count <= left_size {
+            let (before, after) = self.split(self.nodes[node].left, count);
+            self.nodes[node].left = after;
+            self.update(node);
+            (before, Some(node))
+        } else {
+            let (before, after) = self.split(self.nodes[node].right, count - left_size - 1);
+            self.nodes[node].right = before;
+            self.update(node);
+            (Some(node), after)
+        }
+    }
+
+    // the elements of `first` followed by those of `second`.
+    fn merge(&mut self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+        match (first, second) {
+            (None, node) | (node, None) => node,
+            (Some(a), Some(b)) if self.nodes[a].priority > self.nodes[b].priority => {
+                self.nodes[a].right = self.merge(self.nodes[a].right, Some(b));
+                self.update(a);
+                Some(a)
+            }
+            (Some(a), Some(b)) => {
+                self.nodes[b].left = self.merge(Some(a), self.nodes[b].left);
+                self.update(b);
+                Some(b)
+            }
+        }
+    }
+}
+
+/// The value of the element, even if it was removed.
+impl<T> Index<Handle> for IndexedList<T> {
+    type Output = T;
+
+    fn index(&self, handle: Handle) -> &T {
+        &self.nodes[handle.0].value
+    }
+}
+
+impl<T> IndexMut<Handle> for IndexedList<T> {
+    fn index_mut(&mut self, handle: Handle) -> &mut T {
+        &mut self.nodes[handle.0].value
+    }
+}
+
+impl<T> FromIterator<T> for IndexedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = IndexedList::new();
+        for value in iter {
+            result.push(value);
+        }
+        result
+    }
+}
+
+pub struct Iter<'a, T> {
+    list: &'a IndexedList<T>,
+    // the nodes left to visit, along with their right subtrees.
+    stack: Vec<usize>,
+}
+
+impl<T> Iter<'_, T> {
+    fn push_left(&mut self, mut node: Option<usize>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = self.list.nodes[current].left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left(self.list.nodes[node].right);
+        Some(&self.list.nodes[node].value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_indexed_list() {
+        let mut list: IndexedList<char> = "abc".chars().collect();
+        let d = list.insert(1, 'd');
+        assert_eq!(list.iter().collect::<String>(), "adbc");
+        assert_eq!(list.index_of(d), Some(1));
+
+        list.move_to(d, 3);
+        assert_eq!(list.iter().collect::<String>(), "abcd");
+        assert_eq!(list.get(3), Some(&'d'));
+        assert_eq!(list.get(4), None);
+
+        let a = list.handle_at(0).unwrap();
+        assert_eq!(list.remove(a), Some(0));
+        assert_eq!(list.remove(a), None);
+        assert_eq!(list.index_of(a), None);
+        assert_eq!(list[a], 'a');
+        assert_eq!(list.iter().collect::<String>(), "bcd");
+        assert_eq!(list.index_of(d), Some(2));
+    }
+
+    #[test]
+    fn test_move_out_of_bounds() {
+        let mut list: IndexedList<char> = "abc".chars().collect();
+        let a = list.handle_at(0).unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| list.move_to(a, 3)));
+        assert!(result.is_err());
+        // the element stays where it was.
+        assert_eq!(list.iter().collect::<String>(), "abc");
+        assert_eq!(list.index_of(a), Some(0));
+    }
+
+    #[test]
+    fn test_against_vec() {
+        let mut rng = ChaCha8Rng::seed_from_u64(20);
+        let mut list = IndexedList::new();
+        let mut expected: Vec<Handle> = vec![];
+
+        for step in 0..2000 {
+            if expected.is_empty() || rng.gen_bool(0.4) {
+                let index = rng.gen_range(0..=expected.len());
+                expected.insert(index, list.insert(index, step));
+            } else if rng.gen_bool(0.5) {
+                let handle = expected.remove(rng.gen_range(0..expected.len()));
+                let index = rng.gen_range(0..=expected.len());
+                list.move_to(handle, index);
+                expected.insert(index, handle);
+            } else {
+                let handle = expected.remove(rng.gen_range(0..expected.len()));
+                list.remove(handle);
+            }
+
+            if let Some(probe) = expected.get(rng.gen_range(0..=expected.len())) {
+                let position = expected.iter().position(|h| h == probe);
+                assert_eq!(list.index_of(*probe), position);
+            }
+        }
+
+        assert_eq!(list.len(), expected.len());
+        let values: Vec<usize> = expected.iter().map(|h| list[*h]).collect();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), values);
+    }
+}
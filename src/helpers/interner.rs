@@ -0,0 +1,90 @@
+/*
+ * Gives dense indices to labels such as valve names, in order of first appearance,
+ * so that they can be used as `BitSet` elements or `Table` indices.
+ */
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+use std::ops::Index;
+
+#[derive(Clone, Debug, Default)]
+pub struct Interner<T> {
+    ids: FxHashMap<T, usize>,
+    values: Vec<T>,
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    pub fn new() -> Self {
+        Interner {
+            ids: FxHashMap::default(),
+            values: vec![],
+        }
+    }
+
+    /// The index of `value`, giving it the next one if it is new.
+    pub fn intern(&mut self, value: T) -> usize {
+        if let Some(id) = self.ids.get(&value) {
+            return *id;
+        }
+        let id = self.values.len();
+        self.ids.insert(value.clone(), id);
+        self.values.push(value);
+        id
+    }
+
+    /// The index of `value`, `None` if it was never interned.
+    pub fn get(&self, value: &T) -> Option<usize> {
+        self.ids.get(value).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The values in order of their indices.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+/// The value with the given index.
+impl<T> Index<usize> for Interner<T> {
+    type Output = T;
+
+    fn index(&self, id: usize) -> &T {
+        &self.values[id]
+    }
+}
+
+impl<T: Clone + Eq + Hash> FromIterator<T> for Interner<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Interner::new();
+        for value in iter {
+            result.intern(value);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interner() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("AA"), 0);
+        assert_eq!(names.intern("BB"), 1);
+        assert_eq!(names.intern("AA"), 0);
+        assert_eq!(names.get(&"BB"), Some(1));
+        assert_eq!(names.get(&"CC"), None);
+        assert_eq!(names[1], "BB");
+        assert_eq!(names.len(), 2);
+
+        let collected: Interner<&str> = ["x", "y", "x", "z"].into_iter().collect();
+        assert_eq!(collected.values(), ["x", "y", "z"]);
+    }
+}
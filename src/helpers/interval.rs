@@ -0,0 +1,336 @@
+/*
+ * Inclusive integer intervals, and sets of them kept sorted and merged.
+ * Intervals are never empty: `start <= end` always holds.
+ */
+use super::num::Integer;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// Panics if `end` is before `start`.
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "empty interval {:?}..={:?}", start, end);
+        Interval { start, end }
+    }
+
+    /// `None` if `end` is before `start`.
+    pub fn try_new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// Number of integers inside.
+    pub fn len(&self) -> T {
+        self.end - self.start + T::ONE
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether `other` lies entirely inside.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Overlapping or right next to each other, so that their union is an interval.
+    pub fn touches(&self, other: &Self) -> bool {
+        let after =
+            |a: &Self, b: &Self| a.end.checked_add(T::ONE).is_some_and(|next| next < b.start);
+        !after(self, other) && !after(other, self)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Interval::try_new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The smallest interval containing both.
+    pub fn hull(&self, other: &Self) -> Self {
+        Interval::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseIntervalError(String);
+
+impl fmt::Display for ParseIntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse interval from \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseIntervalError {}
+
+/// Parses `start-end`, where `start` may be negative.
+impl<T: Integer> FromStr for Interval<T> {
+    type Err = ParseIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseIntervalError(s.to_string());
+        let dash = s
+            .get(1..)
+            .and_then(|rest| rest.find('-'))
+            .ok_or_else(error)?
+            + 1;
+        let start = s[..dash].trim().parse().map_err(|_| error())?;
+        let end = s[dash + 1..].trim().parse().map_err(|_| error())?;
+        Interval::try_new(start, end).ok_or_else(error)
+    }
+}
+
+/// Disjoint intervals, sorted and with touching intervals merged.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut merged = interval;
+        let mut result = Vec::with_capacity(self.intervals.len() + 1);
+        let mut placed = false;
+
+        for current in self.intervals.drain(..) {
+            if current.touches(&merged) {
+                merged = merged.hull(&current);
+            } else if current.end < merged.start {
+                result.push(current);
+            } else {
+                if !placed {
+                    result.push(merged);
+                    placed = true;
+                }
+                result.push(current);
+            }
+        }
+        if !placed {
+            result.push(merged);
+        }
+
+        self.intervals = result;
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let mut result = Vec::with_capacity(self.intervals.len() + 1);
+
+        for current in self.intervals.drain(..) {
+            if !current.overlaps(&interval) {
+                result.push(current);
+                continue;
+            }
+            if let Some(before) = interval.start.checked_sub(T::ONE) {
+                result.extend(Interval::try_new(current.start, before));
+            }
+            if let Some(after) = interval.end.checked_add(T::ONE) {
+                result.extend(Interval::try_new(after, current.end));
+            }
+        }
+
+        self.intervals = result;
+    }
+
+    // the interval that could contain `value`: the last one starting at or before it.
+    fn candidate(&self, value: T) -> Option<&Interval<T>> {
+        let index = self.intervals.partition_point(|i| i.start <= value);
+        index.checked_sub(1).map(|i| &self.intervals[i])
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.candidate(value).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        self.candidate(interval.start)
+            .is_some_and(|i| i.contains_interval(interval))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut a, mut b) = (0, 0);
+
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (left, right) = (&self.intervals[a], &other.intervals[b]);
+            intervals.extend(left.intersection(right));
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Everything inside `within` that is not in the set.
+    pub fn complement(&self, within: Interval<T>) -> Self {
+        let mut intervals = vec![];
+        let mut cursor = Some(within.start);
+
+        for current in self.intervals.iter().filter(|i| i.overlaps(&within)) {
+            let Some(start) = cursor else { break };
+            if let Some(before) = current.start.checked_sub(T::ONE) {
+                intervals.extend(Interval::try_new(start, before));
+            }
+            cursor = current.end.checked_add(T::ONE);
+        }
+        if let Some(start) = cursor {
+            intervals.extend(Interval::try_new(start, within.end));
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// The holes between the intervals, from the first to the last one.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.windows(2).map(|pair| {
+            Interval::new(
+                pair[0].end.checked_add(T::ONE).unwrap(),
+                pair[1].start.checked_sub(T::ONE).unwrap(),
+            )
+        })
+    }
+
+    /// Number of integers inside.
+    pub fn covered_len(&self) -> T {
+        self.intervals.iter().fold(T::ZERO, |acc, i| acc + i.len())
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut result = IntervalSet::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<T: Integer> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(a, b)| Interval::new(a, b))
+            .collect()
+    }
+
+    fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 6);
+        let b = Interval::new(4, 8);
+        assert_eq!(a.len(), 5);
+        assert!(a.contains(2) && a.contains(6) && !a.contains(7));
+        assert!(a.overlaps(&b));
+        assert!(!a.contains_interval(&b));
+        assert!(a.contains_interval(&Interval::new(3, 6)));
+        assert_eq!(a.intersection(&b), Some(Interval::new(4, 6)));
+        assert_eq!(a.intersection(&Interval::new(7, 9)), None);
+        assert!(a.touches(&Interval::new(7, 9)));
+        assert!(!a.touches(&Interval::new(8, 9)));
+        assert!(Interval::new(0_u8, 255).touches(&Interval::new(255, 255)));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2-4".parse(), Ok(Interval::new(2_u32, 4)));
+        assert_eq!("-5--2".parse(), Ok(Interval::new(-5, -2)));
+        assert!("4-2".parse::<Interval<u32>>().is_err());
+        assert!("4".parse::<Interval<u32>>().is_err());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[(10, 12), (0, 2)]);
+        assert_eq!(pairs(&s), vec![(0, 2), (10, 12)]);
+
+        s.insert(Interval::new(3, 4));
+        assert_eq!(pairs(&s), vec![(0, 4), (10, 12)]);
+        s.insert(Interval::new(6, 7));
+        assert_eq!(pairs(&s), vec![(0, 4), (6, 7), (10, 12)]);
+        s.insert(Interval::new(5, 9));
+        assert_eq!(pairs(&s), vec![(0, 12)]);
+        s.insert(Interval::new(20, 20));
+        assert_eq!(pairs(&s), vec![(0, 12), (20, 20)]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut s = set(&[(0, 10), (20, 30)]);
+        s.remove(Interval::new(5, 22));
+        assert_eq!(pairs(&s), vec![(0, 4), (23, 30)]);
+        s.remove(Interval::new(0, 0));
+        assert_eq!(pairs(&s), vec![(1, 4), (23, 30)]);
+        s.remove(Interval::new(-10, 100));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let s = set(&[(0, 4), (10, 12)]);
+        assert!(s.contains(0) && s.contains(11));
+        assert!(!s.contains(5) && !s.contains(-1) && !s.contains(13));
+        assert!(s.contains_interval(&Interval::new(1, 3)));
+        assert!(!s.contains_interval(&Interval::new(3, 10)));
+        assert_eq!(s.covered_len(), 8);
+        assert_eq!(s.gaps().collect::<Vec<_>>(), vec![Interval::new(5, 9)]);
+    }
+
+    #[test]
+    fn test_intersection_and_complement() {
+        let a = set(&[(0, 4), (10, 12), (20, 25)]);
+        let b = set(&[(3, 11), (24, 30)]);
+        assert_eq!(pairs(&a.intersection(&b)), vec![(3, 4), (10, 11), (24, 25)]);
+
+        let within = Interval::new(-2, 22);
+        assert_eq!(
+            pairs(&a.complement(within)),
+            vec![(-2, -1), (5, 9), (13, 19)]
+        );
+        assert!(set(&[(0, 10)]).complement(Interval::new(2, 8)).is_empty());
+        assert_eq!(
+            pairs(&IntervalSet::new().complement(within)),
+            vec![(-2, 22)]
+        );
+
+        let full = IntervalSet::from_iter([Interval::new(0_u8, 255)]);
+        assert!(full.complement(Interval::new(0, 255)).is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * Memoized recursion and dense tables for dynamic programming.
+ * `Memo` wraps a recursive function, which calls itself through the function it is given
+ * as first argument; a key that depends on itself is reported instead of overflowing the stack.
+ * `Table` is a flat array indexed by tuples of small integers, e.g. `(valve, minute)`.
+ */
+use rustc_hash::FxHashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
+
+/// A key whose value ended up depending on itself.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CycleError<K> {
+    pub key: K,
+}
+
+impl<K: fmt::Debug> fmt::Display for CycleError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} depends on itself", self.key)
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for CycleError<K> {}
+
+pub type MemoResult<K, V> = Result<V, CycleError<K>>;
+
+pub struct Memo<K, V, F> {
+    f: F,
+    // `None` while the value of the key is being computed.
+    cache: FxHashMap<K, Option<V>>,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> MemoResult<K, V>, K) -> MemoResult<K, V>,
+{
+    /// `f` receives the function to use for recursive calls, and the key to compute.
+    pub fn new(f: F) -> Self {
+        Memo {
+            f,
+            cache: FxHashMap::default(),
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> MemoResult<K, V> {
+        compute(&self.f, &mut self.cache, key)
+    }
+
+    /// Number of values computed so far.
+    pub fn len(&self) -> usize {
+        self.cache.values().filter(|v| v.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// a free function, so that `f` can be borrowed while `cache` is borrowed mutably.
+fn compute<K, V, F>(f: &F, cache: &mut FxHashMap<K, Option<V>>, key: K) -> MemoResult<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> MemoResult<K, V>, K) -> MemoResult<K, V>,
+{
+    match cache.get(&key) {
+        Some(Some(value)) => return Ok(value.clone()),
+        Some(None) => return Err(CycleError { key }),
+        None => {}
+    }
+
+    cache.insert(key.clone(), None);
+    let result = f(&mut |next| compute(f, cache, next), key.clone());
+    match &result {
+        Ok(value) => cache.insert(key, Some(value.clone())),
+        // leave the key free, so that a later call reports the cycle again.
+        Err(_) => cache.remove(&key),
+    };
+    result
+}
+
+/// Indices of a `Table`: a `usize` or a tuple of up to four of them.
+pub trait TableIndex: Copy + fmt::Debug {
+    /// Position in the flat array, `None` if out of `dims`.
+    fn offset(self, dims: Self) -> Option<usize>;
+    /// Number of cells of a table with these dimensions.
+    fn size(dims: Self) -> usize;
+}
+
+impl TableIndex for usize {
+    fn offset(self, dims: Self) -> Option<usize> {
+        (self < dims).then_some(self)
+    }
+
+    fn size(dims: Self) -> usize {
+        dims
+    }
+}
+
+// row-major: the last component varies the fastest.
+fn flat_offset(index: &[usize], dims: &[usize]) -> Option<usize> {
+    index
+        .iter()
+        .zip(dims)
+        .try_fold(0, |acc, (&i, &dim)| (i < dim).then_some(acc * dim + i))
+}
+
+macro_rules! impl_table_index {
+    ($t:ty, $($n:tt),+) => {
+        impl TableIndex for $t {
+            fn offset(self, dims: Self) -> Option<usize> {
+                flat_offset(&[$(self.$n),+], &[$(dims.$n),+])
+            }
+
+            fn size(dims: Self) -> usize {
+                1 $(* dims.$n)+
+            }
+        }
+    };
+}
+
+impl_table_index!((usize, usize), 0, 1);
+impl_table_index!((usize, usize, usize), 0, 1, 2);
+impl_table_index!((usize, usize, usize, usize), 0, 1, 2, 3);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table<I, V> {
+    dims: I,
+    values: Vec<V>,
+}
+
+impl<I: TableIndex, V: Clone> Table<I, V> {
+    /// A table with `dims` cells along each axis, all set to `value`.
+    pub fn new(dims: I, value: V) -> Self {
+        Table {
+            dims,
+            values: vec![value; I::size(dims)],
+        }
+    }
+}
+
+impl<I: TableIndex, V> Table<I, V> {
+    pub fn dims(&self) -> I {
+        self.dims
+    }
+
+    pub fn get(&self, index: I) -> Option<&V> {
+        index.offset(self.dims).map(|i| &self.values[i])
+    }
+
+    pub fn get_mut(&mut self, index: I) -> Option<&mut V> {
+        index.offset(self.dims).map(|i| &mut self.values[i])
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.values.iter()
+    }
+
+    /// Stores `value` if it beats the one at `index`, returns whether it did.
+    pub fn improve(&mut self, index: I, value: V, better: impl Fn(&V, &V) -> bool) -> bool {
+        let current = &mut self[index];
+        if better(&value, current) {
+            *current = value;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<I: TableIndex, V> Index<I> for Table<I, V> {
+    type Output = V;
+
+    fn index(&self, index: I) -> &V {
+        match index.offset(self.dims) {
+            Some(i) => &self.values[i],
+            None => panic!("{:?} is out of a table of {:?}", index, self.dims),
+        }
+    }
+}
+
+impl<I: TableIndex, V> IndexMut<I> for Table<I, V> {
+    fn index_mut(&mut self, index: I) -> &mut V {
+        match index.offset(self.dims) {
+            Some(i) => &mut self.values[i],
+            None => panic!("{:?} is out of a table of {:?}", index, self.dims),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memo() {
+        let mut fib =
+            Memo::new(|fib, n: u64| Ok(if n < 2 { n } else { fib(n - 1)? + fib(n - 2)? }));
+        assert_eq!(fib.get(90), Ok(2_880_067_194_370_816_120));
+        assert_eq!(fib.len(), 91);
+    }
+
+    #[test]
+    fn test_memo_cycle() {
+        // a -> b -> c -> a, while d only depends on literals.
+        let deps = |name: &str| match name {
+            "a" => Some("b"),
+            "b" => Some("c"),
+            "c" => Some("a"),
+            _ => None,
+        };
+        let mut eval = Memo::new(|eval, name: &'static str| match deps(name) {
+            Some(next) => Ok(eval(next)? + 1),
+            None => Ok(0),
+        });
+
+        assert_eq!(eval.get("d"), Ok(0));
+        assert_eq!(eval.get("b"), Err(CycleError { key: "b" }));
+        assert_eq!(eval.get("a"), Err(CycleError { key: "a" }));
+        assert_eq!(eval.len(), 1);
+    }
+
+    #[test]
+    fn test_table() {
+        let mut table = Table::new((2, 3, 4), 0_u32);
+        assert_eq!(table.values().count(), 24);
+        table[(1, 2, 3)] = 7;
+        assert_eq!(table.get((1, 2, 3)), Some(&7));
+        assert_eq!(table.get((1, 3, 0)), None);
+        assert_eq!(table.values().sum::<u32>(), 7);
+
+        assert!(table.improve((0, 0, 0), 5, |new, old| new > old));
+        assert!(!table.improve((0, 0, 0), 4, |new, old| new > old));
+        assert_eq!(table[(0, 0, 0)], 5);
+
+        let mut line = Table::new(3, 'a');
+        line[2] = 'c';
+        assert_eq!(line.values().collect::<String>(), "aac");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_table_out_of_bounds() {
+        let table = Table::new((2, 2), 0);
+        let _cell = table[(2, 0)];
+    }
+}
@@ -0,0 +1,342 @@
+/*
+ * Number theory over the primitive integer types: gcd and lcm, modular arithmetic and
+ * the chinese remainder theorem, e.g. to find when several cycles line up again.
+ * Results are never negative, even for negative arguments of signed types.
+ * `Integer` and `Signed` are also what points and intervals are made of.
+ * example: `let period = lcm_all(monkeys.iter().map(|m| m.divisor));`
+ */
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// `None` when dividing by zero too.
+    fn checked_div(self, other: Self) -> Option<Self>;
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    /// The remainder of the division by `modulus`, between 0 and `modulus`.
+    fn rem_euclid(self, modulus: Self) -> Self;
+
+    /// `self * other` modulo `modulus`, even when the product itself would overflow.
+    fn mul_mod(self, other: Self, modulus: Self) -> Self;
+
+    /// The largest integer whose square is at most `self`, panics if `self` is negative.
+    fn isqrt(self) -> Self;
+
+    fn to_f64(self) -> f64;
+}
+
+/// The integer types that have negative numbers, needed for Bézout coefficients or vectors.
+pub trait Signed: Integer + Neg<Output = Self> {
+    fn abs(self) -> Self;
+    /// -1, 0 or 1.
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($wide:ty; $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_div(self, other: Self) -> Option<Self> {
+                <$t>::checked_div(self, other)
+            }
+
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn rem_euclid(self, modulus: Self) -> Self {
+                <$t>::rem_euclid(self, modulus)
+            }
+
+            fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                (self as $wide * other as $wide).rem_euclid(modulus as $wide) as $t
+            }
+
+            fn isqrt(self) -> Self {
+                <$t>::isqrt(self)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+// 128 bits have nothing wider to go to, so they double and add instead.
+macro_rules! impl_integer_128 {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_div(self, other: Self) -> Option<Self> {
+                <$t>::checked_div(self, other)
+            }
+
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn rem_euclid(self, modulus: Self) -> Self {
+                <$t>::rem_euclid(self, modulus)
+            }
+
+            fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                let add_mod = |a: Self, b: Self| {
+                    if a >= modulus - b {
+                        a - (modulus - b)
+                    } else {
+                        a + b
+                    }
+                };
+                let (mut a, mut b) = (self.rem_euclid(modulus), other.rem_euclid(modulus));
+                let mut result = 0;
+                while b > 0 {
+                    if b % 2 == 1 {
+                        result = add_mod(result, a);
+                    }
+                    a = add_mod(a, a);
+                    b /= 2;
+                }
+                result
+            }
+
+            fn isqrt(self) -> Self {
+                <$t>::isqrt(self)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+impl_integer!(u128; u8, u16, u32, u64, usize);
+impl_integer!(i128; i8, i16, i32, i64, isize);
+impl_integer_128!(u128, i128);
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Signed for $t {
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+fn abs<T: Integer>(value: T) -> T {
+    if value < T::ZERO {
+        T::ZERO - value
+    } else {
+        value
+    }
+}
+
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// The smallest positive multiple of both, 0 if either is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+/// The gcd of all the numbers, 0 if there are none.
+pub fn gcd_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    numbers.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The lcm of all the numbers, 1 if there are none.
+pub fn lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    numbers.into_iter().fold(T::ONE, lcm)
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, `g` being the gcd of `a` and `b`.
+pub fn ext_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` for which `a * x` is 1 modulo `modulus`, if they are coprime.
+pub fn modinv<T: Signed>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(modulus), modulus);
+    (g == T::ONE).then(|| x.rem_euclid(modulus))
+}
+
+/// `base` to the power of `exp` modulo `modulus`, by squaring.
+pub fn modpow<T: Integer>(base: T, exp: T, modulus: T) -> T {
+    let two = T::ONE + T::ONE;
+    let (mut base, mut exp) = (base.rem_euclid(modulus), exp);
+    let mut result = T::ONE.rem_euclid(modulus);
+
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exp = exp / two;
+    }
+
+    result
+}
+
+pub fn isqrt<T: Integer>(n: T) -> T {
+    n.isqrt()
+}
+
+/// The `x` in `0..m` that satisfies every `x ≡ residue (mod modulus)`, `m` being the lcm of the
+/// moduli, which don't need to be coprime.
+/// `None` if the congruences contradict each other, or if `m` does not fit in `T`.
+pub fn crt<T: Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let mut result = (T::ZERO, T::ONE);
+
+    for (residue, modulus) in congruences {
+        let (x, m) = result;
+        let (g, inverse, _) = ext_gcd(m, modulus);
+        let diff = (residue - x).rem_euclid(modulus);
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        // x + m * t is a solution for t = diff / g * inverse, modulo modulus / g.
+        let step = modulus / g;
+        let t = (diff / g).mul_mod(inverse, step);
+        let combined = (m / g).checked_mul(modulus)?;
+        result = ((x + m.mul_mod(t, combined)).rem_euclid(combined), combined);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i64, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(lcm_all([2u64, 3, 5, 7, 11, 13, 17, 19]), 9699690);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for (a, b) in [(240i64, 46), (-240, 46), (17, 0), (3, 7)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(modinv(3, 11), Some(4));
+        assert_eq!(modinv(-3i32, 11), Some(7));
+        assert_eq!(modinv(6, 9), None);
+    }
+
+    #[test]
+    fn test_modpow() {
+        assert_eq!(modpow(4u32, 13, 497), 445);
+        assert_eq!(modpow(2u64, 0, 1), 0);
+        assert_eq!(modpow(-2i64, 3, 5), 2);
+        // the products overflow u64 and i128 without the wide multiplication.
+        let p = (1u64 << 61) - 1;
+        assert_eq!(modpow(3, p - 1, p), 1);
+        let q = (1i128 << 107) - 1;
+        assert_eq!(modpow(5, q - 1, q), 1);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0u32), 0);
+        assert_eq!(isqrt(24i64), 4);
+        assert_eq!(isqrt(25usize), 5);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(-1i64, 4), (3, 6)]), Some((3, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt(Vec::<(i32, i32)>::new()), Some((0, 1)));
+        assert_eq!(crt([(0i8, 11), (0, 13)]), None);
+    }
+}
@@ -0,0 +1,307 @@
+/*
+ * Reads the block letters that some puzzles draw instead of printing their answer,
+ * such as the CRT screen of day 10.
+ * Letters of the small font are 4×6 pixels with one blank column between them,
+ * those of the large font are 6×10 pixels with two blank columns between them.
+ * example: `let answer = ocr::recognize(&screen)?;` with `screen: Grid<bool>`.
+ */
+use super::Grid;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// No font is that many pixels high.
+    Height(usize),
+    /// The letter at `index` is not part of the font, drawn with `#` and `.`.
+    Unknown { index: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => write!(f, "no font is {} pixels high", height),
+            OcrError::Unknown { index, glyph } => {
+                write!(f, "unknown glyph for letter {}:\n{}", index, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+pub struct Font {
+    pub width: usize,
+    pub height: usize,
+    /// Blank columns between two letters.
+    pub spacing: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+pub const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    spacing: 1,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        // the right arm of the Y reaches into the blank column, which is never read.
+        ('Y', &["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+pub const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    spacing: 2,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+impl Font {
+    /// The letters drawn on `screen`, from left to right.
+    /// Columns after the last whole letter are ignored.
+    pub fn recognize(&self, screen: &Grid<bool>) -> Result<String, OcrError> {
+        if screen.height() != self.height {
+            return Err(OcrError::Height(screen.height()));
+        }
+        let step = self.width + self.spacing;
+
+        (0..(screen.width() + self.spacing) / step)
+            .map(|index| {
+                let glyph = self.glyph_at(screen, index * step);
+                self.glyphs
+                    .iter()
+                    .find(|(_, rows)| rows.join("\n") == glyph)
+                    .map(|(letter, _)| *letter)
+                    .ok_or(OcrError::Unknown { index, glyph })
+            })
+            .collect()
+    }
+
+    /// Draws `text` the way a puzzle would, `None` if a letter is not part of the font.
+    pub fn render(&self, text: &str) -> Option<Grid<bool>> {
+        let step = self.width + self.spacing;
+        let mut screen = Grid::new(text.chars().count() * step, self.height, false);
+
+        for (index, letter) in text.chars().enumerate() {
+            let (_, rows) = self.glyphs.iter().find(|(l, _)| *l == letter)?;
+            for (y, row) in rows.iter().enumerate() {
+                for (x, pixel) in row.chars().enumerate() {
+                    screen[(index * step + x, y)] = pixel == '#';
+                }
+            }
+        }
+
+        Some(screen)
+    }
+
+    // the letter starting at column `left`, drawn like the glyphs of the font.
+    fn glyph_at(&self, screen: &Grid<bool>, left: usize) -> String {
+        let rows: Vec<String> = (0..self.height)
+            .map(|y| {
+                (left..left + self.width)
+                    .map(|x| if screen[(x, y)] { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        rows.join("\n")
+    }
+}
+
+/// The letters drawn on `screen`, with the font that has its height.
+pub fn recognize(screen: &Grid<bool>) -> Result<String, OcrError> {
+    [SMALL, LARGE]
+        .iter()
+        .find(|font| font.height == screen.height())
+        .ok_or(OcrError::Height(screen.height()))?
+        .recognize(screen)
+}
+
+/// The same as `recognize`, for pixels stored row by row in rows of `width`.
+pub fn recognize_pixels(pixels: &[bool], width: usize) -> Result<String, OcrError> {
+    recognize(&Grid::from_vec(width, pixels.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognize() {
+        let screen = Grid::parse(
+            "\
+###..#..#.####.#....
+#..#.#..#....#.#....
+#..#.####...#..#....
+###..#..#..#...#....
+#.#..#..#.#....#....
+#..#.#..#.####.####.",
+            |c| c == '#',
+        );
+        assert_eq!(recognize(&screen), Ok("RHZL".to_string()));
+        assert_eq!(SMALL.recognize(&screen), Ok("RHZL".to_string()));
+
+        let pixels: Vec<bool> = screen.iter().map(|(_, lit)| *lit).collect();
+        assert_eq!(recognize_pixels(&pixels, 20), Ok("RHZL".to_string()));
+
+        let screen = Grid::parse(
+            "\
+.###.#...#
+..#..#...#
+..#...#.#.
+..#....#..
+..#....#..
+.###...#..",
+            |c| c == '#',
+        );
+        assert_eq!(recognize(&screen), Ok("IY".to_string()));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for font in [SMALL, LARGE] {
+            let text: String = font.glyphs.iter().map(|(letter, _)| *letter).collect();
+            let screen = font.render(&text).unwrap();
+            assert_eq!(screen.height(), font.height);
+            assert_eq!(font.recognize(&screen), Ok(text));
+        }
+        assert_eq!(SMALL.render("AQ"), None);
+    }
+
+    #[test]
+    fn test_unknown() {
+        let mut screen = SMALL.render("ABC").unwrap();
+        screen[(5, 0)] = false;
+        assert_eq!(
+            recognize(&screen),
+            Err(OcrError::Unknown {
+                index: 1,
+                glyph: ".##.\n#..#\n###.\n#..#\n#..#\n###.".to_string()
+            })
+        );
+        assert_eq!(
+            recognize(&Grid::new(40, 7, false)),
+            Err(OcrError::Height(7))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * nom parsers for the building blocks of puzzle inputs: numbers, lists, coordinates,
+ * lines, blank-line separated blocks and character grids.
+ * `parse_all` runs a parser over a whole input and reports where it stopped.
+ * example: `let sums = parse_all(blocks(lines(uint::<u32>)), input)?;`
+ */
+use super::Grid;
+use nom::{
+    bytes::complete::take_while1,
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{map, map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::{pair, separated_pair, tuple},
+    IResult,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// An unsigned integer of any width.
+pub fn uint<T: FromStr>(i: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(i)
+}
+
+/// An integer of any width, with an optional `-` or `+` sign.
+pub fn int<T: FromStr>(i: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(i)
+}
+
+// a comma, optionally followed by spaces.
+fn comma(i: &str) -> IResult<&str, (char, &str)> {
+    pair(char(','), space0)(i)
+}
+
+/// Items separated by commas, as in `1,2,3` or `1, 2, 3`.
+pub fn comma_list<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(comma, item)
+}
+
+/// Items separated by spaces, as in `1 2  3`.
+pub fn space_list<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(space1, item)
+}
+
+/// A pair of integers such as `498,-4`.
+pub fn coord2<T: FromStr>(i: &str) -> IResult<&str, (T, T)> {
+    separated_pair(int, comma, int)(i)
+}
+
+/// A triple of integers such as `2,-2,5`.
+pub fn coord3<T: FromStr>(i: &str) -> IResult<&str, (T, T, T)> {
+    map(tuple((int, comma, int, comma, int)), |(x, _, y, _, z)| {
+        (x, y, z)
+    })(i)
+}
+
+/// One item per line, without the last line ending.
+pub fn lines<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(line_ending, item)
+}
+
+/// Blocks separated by a blank line, each parsed by `block`.
+pub fn blocks<'a, T>(
+    block: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// Lines made of the `allowed` characters, mapped to cells as in `Grid::parse`.
+pub fn grid<'a, T>(
+    allowed: &'static str,
+    mut cell: impl FnMut(char) -> T,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |i| {
+        let (rest, rows) = recognize(lines(take_while1(|c| allowed.contains(c))))(i)?;
+        Ok((rest, Grid::parse(rows, &mut cell)))
+    }
+}
+
+/// Where a parser stopped before the end of its input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The rest of that line.
+    pub found: String,
+}
+
+impl ParseError {
+    // located at the first character that isn't whitespace.
+    fn at(input: &str, rest: &str) -> Self {
+        let rest = rest.trim_start();
+        let offset = input.len() - rest.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            found: rest.lines().next().unwrap_or("").to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not parse line {}, column {}: \"{}\"",
+            self.line, self.column, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over the whole `input`, only trailing whitespace may be left over.
+pub fn parse_all<'a, T>(
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+    input: &'a str,
+) -> Result<T, ParseError> {
+    match parser(input) {
+        Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+        Ok((rest, _)) => Err(ParseError::at(input, rest)),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::at(input, e.input)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(input, "")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::bytes::complete::tag;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(uint::<u8>("255 "), Ok((" ", 255)));
+        assert!(uint::<u8>("256").is_err());
+        assert!(uint::<u32>("-1").is_err());
+        assert_eq!(int::<i64>("-12,"), Ok((",", -12)));
+        assert_eq!(int::<i32>("+7"), Ok(("", 7)));
+        assert_eq!(
+            int::<u128>("340282366920938463463374607431768211455")
+                .unwrap()
+                .1,
+            u128::MAX
+        );
+        assert!(int::<i32>("-").is_err());
+    }
+
+    #[test]
+    fn test_lists_and_coords() {
+        assert_eq!(
+            comma_list(uint::<u32>)("1,2, 3\n"),
+            Ok(("\n", vec![1, 2, 3]))
+        );
+        assert_eq!(space_list(int::<i32>)("1  -2 3"), Ok(("", vec![1, -2, 3])));
+        assert_eq!(coord2::<i32>("498,-4 ->"), Ok((" ->", (498, -4))));
+        assert_eq!(coord3::<i8>("2, -2,5"), Ok(("", (2, -2, 5))));
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let input = "1\n2\n\n3\n";
+        assert_eq!(
+            parse_all(blocks(lines(uint::<u32>)), input),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(parse_all(lines(int::<i32>), "1\r\n-2"), Ok(vec![1, -2]));
+    }
+
+    #[test]
+    fn test_grid() {
+        let (rest, grid) = grid(".#", |c| c == '#')("#.\n.#\n\n5").unwrap();
+        assert_eq!(rest, "\n\n5");
+        assert_eq!(grid.width(), 2);
+        assert!(grid[(1, 1)] && !grid[(1, 0)]);
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(parse_all(tag("ab"), "ab\n"), Ok("ab"));
+        assert_eq!(
+            parse_all(lines(uint::<u32>), "1\n2\n3x\n"),
+            Err(ParseError {
+                line: 3,
+                column: 2,
+                found: "x".to_string()
+            })
+        );
+        let error = parse_all(lines(uint::<u32>), "1\nfoo").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "could not parse line 2, column 1: \"foo\""
+        );
+    }
+}
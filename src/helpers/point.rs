@@ -0,0 +1,329 @@
+/*
+ * 2D and 3D points / vectors over signed integers.
+ * Both parse from and print as `x,y` (or `x,y,z`); the alternate form `{:#}` prints `x=.., y=..`,
+ * which parsing accepts as well.
+ */
+use super::num::Signed;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePointError(String);
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse point from \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+// the coordinates of `x,y,...` or `x=.., y=.., ...`, in order.
+fn parse_coords<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], ParsePointError> {
+    let names = ["x", "y", "z"];
+    let error = || ParsePointError(s.to_string());
+    let parts: Vec<&str> = s.split(',').map(|p| p.trim()).collect();
+    if parts.len() != N {
+        return Err(error());
+    }
+
+    let mut values = Vec::with_capacity(N);
+    for (name, part) in names.iter().zip(parts) {
+        let value = match part.split_once('=') {
+            Some((key, value)) if key.trim() == *name => value.trim(),
+            Some(_) => return Err(error()),
+            None => part,
+        };
+        values.push(value.parse().map_err(|_| error())?);
+    }
+
+    values.try_into().map_err(|_| error())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Signed> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn euclidean(self, other: Self) -> f64 {
+        let (dx, dy) = ((self.x - other.x).to_f64(), (self.y - other.y).to_f64());
+        dx.hypot(dy)
+    }
+
+    /// Each coordinate replaced by its sign: the unit step that goes towards `self`.
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// A quarter turn counter-clockwise, with the y axis pointing up.
+    /// With the y axis pointing down (as in puzzle maps) this turns clockwise.
+    pub fn rotate_left(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// A quarter turn clockwise, with the y axis pointing up.
+    pub fn rotate_right(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+}
+
+impl<T: Signed> FromStr for Point2<T> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_coords(s)?;
+        Ok(Point2 { x, y })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "x={}, y={}", self.x, self.y)
+        } else {
+            write!(f, "{},{}", self.x, self.y)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Signed> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn euclidean(self, other: Self) -> f64 {
+        let (dx, dy, dz) = (
+            (self.x - other.x).to_f64(),
+            (self.y - other.y).to_f64(),
+            (self.z - other.z).to_f64(),
+        );
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// Quarter turns counter-clockwise around each axis, looking from its positive end.
+    pub fn rotate_x(self) -> Self {
+        Point3::new(self.x, -self.z, self.y)
+    }
+
+    pub fn rotate_y(self) -> Self {
+        Point3::new(self.z, self.y, -self.x)
+    }
+
+    pub fn rotate_z(self) -> Self {
+        Point3::new(-self.y, self.x, self.z)
+    }
+
+    /// The six points sharing a face with `self`.
+    pub fn neighbours(self) -> [Self; 6] {
+        let (one, zero) = (T::ONE, T::ZERO);
+        [
+            Point3::new(one, zero, zero),
+            Point3::new(-one, zero, zero),
+            Point3::new(zero, one, zero),
+            Point3::new(zero, -one, zero),
+            Point3::new(zero, zero, one),
+            Point3::new(zero, zero, -one),
+        ]
+        .map(|offset| self + offset)
+    }
+}
+
+impl<T: Signed> FromStr for Point3<T> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_coords(s)?;
+        Ok(Point3 { x, y, z })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "x={}, y={}, z={}", self.x, self.y, self.z)
+        } else {
+            write!(f, "{},{},{}", self.x, self.y, self.z)
+        }
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident, $($field:ident),+) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        /// Scaling by a factor.
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                $point { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl<T: Add<Output = T> + Copy> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Sub<Output = T> + Copy> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> MulAssign<T> for $point<T> {
+            fn mul_assign(&mut self, factor: T) {
+                *self = *self * factor;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2, x, y);
+impl_ops!(Point3, x, y, z);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let mut p = Point2::new(1, 2);
+        assert_eq!(p + Point2::new(3, -1), Point2::new(4, 1));
+        assert_eq!(p - Point2::new(3, -1), Point2::new(-2, 3));
+        assert_eq!(-p, Point2::new(-1, -2));
+        assert_eq!(p * 3, Point2::new(3, 6));
+
+        p += Point2::new(1, 1);
+        p -= Point2::new(0, 2);
+        p *= 2;
+        assert_eq!(p, Point2::new(4, 2));
+
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2,
+            Point3::new(3, 4, 5)
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point2::new(0, 0), Point2::new(3, -4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+
+        let (a, b) = (Point3::new(1, 1, 1), Point3::new(3, -1, 2));
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 2);
+        assert_eq!(a.euclidean(b), 3.0);
+    }
+
+    #[test]
+    fn test_signum() {
+        assert_eq!(Point2::new(-7, 0).signum(), Point2::new(-1, 0));
+        assert_eq!(Point3::new(2, -3, 0).signum(), Point3::new(1, -1, 0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let p = Point2::new(2, 1);
+        assert_eq!(p.rotate_left(), Point2::new(-1, 2));
+        assert_eq!(p.rotate_right(), Point2::new(1, -2));
+        assert_eq!(p.rotate_left().rotate_right(), p);
+        assert_eq!(p.rotate_left().rotate_left(), -p);
+
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p.rotate_z(), Point3::new(-2, 1, 3));
+        assert_eq!(p.rotate_x().rotate_x().rotate_x().rotate_x(), p);
+        assert_eq!(p.rotate_y().rotate_y(), Point3::new(-1, 2, -3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let neighbours = Point3::new(0, 0, 0).neighbours();
+        assert!(neighbours
+            .iter()
+            .all(|n| n.manhattan(Point3::default()) == 1));
+        assert_eq!(neighbours.len(), 6);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!("3,-4".parse(), Ok(Point2::new(3, -4)));
+        assert_eq!("x=3, y=-4".parse(), Ok(Point2::new(3_i64, -4)));
+        assert_eq!("1,2,3".parse(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!("x=1, y=2, z=3".parse(), Ok(Point3::new(1, 2, 3)));
+
+        assert!("1,2,3".parse::<Point2<i32>>().is_err());
+        assert!("y=1, x=2".parse::<Point2<i32>>().is_err());
+        assert!("a,b".parse::<Point2<i32>>().is_err());
+
+        assert_eq!(Point2::new(3, -4).to_string(), "3,-4");
+        assert_eq!(format!("{:#}", Point2::new(3, -4)), "x=3, y=-4");
+        assert_eq!(format!("{:#}", Point3::new(1, 2, 3)), "x=1, y=2, z=3");
+    }
+}
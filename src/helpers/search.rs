@@ -0,0 +1,266 @@
+/*
+ * Shortest path searches over implicit graphs, described by a successors function.
+ * Every search starts from any number of nodes, stops at the first node accepted by `is_goal`
+ * and returns the path from a start to that node (both included) along with its cost.
+ * example: `bfs_cost([start], |pos| neighbours(pos), |pos| *pos == end)` when only the cost matters
+ */
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Path costs, `Default` being a cost of zero.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+// the path ending at `goal`, following parents back to a start.
+fn rebuild_path<N: Clone + Eq + Hash>(parents: &FxHashMap<N, N>, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+    while let Some(prev) = parents.get(path.last().unwrap()) {
+        path.push(prev.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search, for graphs where every edge costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: FxHashMap<N, N> = FxHashMap::default();
+    let (goal, cost) = breadth_first(starts, successors, is_goal, |node, next| {
+        parents.insert(next.clone(), node.clone());
+    })?;
+    Some((rebuild_path(&parents, goal), cost))
+}
+
+/// Breadth-first search returning only the cost, without keeping the parents of every node.
+pub fn bfs_cost<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(starts, successors, is_goal, |_, _| {}).map(|(_, cost)| cost)
+}
+
+// the goal reached and its cost, `on_edge` sees every edge to a node seen for the first time.
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut on_edge: impl FnMut(&N, &N),
+) -> Option<(N, usize)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen: FxHashSet<N> = FxHashSet::default();
+    let mut to_visit = VecDeque::new();
+
+    for start in starts {
+        if seen.insert(start.clone()) {
+            to_visit.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = to_visit.pop_front() {
+        if is_goal(&node) {
+            return Some((node, cost));
+        }
+
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                on_edge(&node, &next);
+                to_visit.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm, successors come with the cost of reaching them.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+#[derive(Debug)]
+struct HeapState<N, C> {
+    node: N,
+    cost: C,
+    // cost plus the estimate to the goal.
+    priority: C,
+}
+
+// Manually implement Ord so we get a min-heap instead of a max-heap
+impl<N, C: Ord> Ord for HeapState<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for HeapState<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for HeapState<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for HeapState<N, C> {}
+
+/// A* search, `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best: FxHashMap<N, C> = FxHashMap::default();
+    let mut parents: FxHashMap<N, N> = FxHashMap::default();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        let zero = C::default();
+        best.insert(start.clone(), zero);
+        heap.push(HeapState {
+            priority: heuristic(&start),
+            node: start,
+            cost: zero,
+        });
+    }
+
+    while let Some(HeapState { node, cost, .. }) = heap.pop() {
+        // a cheaper way to this node was found after this entry was pushed.
+        if best.get(&node).is_some_and(|b| *b < cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some((rebuild_path(&parents, node), cost));
+        }
+
+        for (next, next_cost) in successors(&node) {
+            let new_cost = cost + next_cost;
+
+            if best.get(&next).is_none_or(|b| new_cost < *b) {
+                best.insert(next.clone(), new_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(HeapState {
+                    priority: new_cost + heuristic(&next),
+                    node: next,
+                    cost: new_cost,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a line of numbers where you can step by one or jump to the double.
+    fn line_successors(n: &u32) -> Vec<(u32, u32)> {
+        vec![(n + 1, 1), (n * 2, 3)]
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs([1], |n| [n + 1, n * 2], |n| *n == 10);
+        assert_eq!(result, Some((vec![1, 2, 4, 5, 10], 4)));
+
+        // the closest of several starts wins.
+        let result = bfs([1, 8], |n| [n + 1, n * 2], |n| *n == 10);
+        assert_eq!(result, Some((vec![8, 9, 10], 2)));
+
+        assert_eq!(
+            bfs([1], |n| [n + 1], |n| *n == 0 || *n > 5),
+            Some((vec![1, 2, 3, 4, 5, 6], 5))
+        );
+        assert_eq!(bfs([1], |_| [], |n| *n == 2), None);
+    }
+
+    #[test]
+    fn test_bfs_cost() {
+        assert_eq!(bfs_cost([1], |n| [n + 1, n * 2], |n| *n == 10), Some(4));
+        assert_eq!(bfs_cost([1, 8], |n| [n + 1, n * 2], |n| *n == 10), Some(2));
+        assert_eq!(bfs_cost([3], |n| [n + 1], |n| *n == 3), Some(0));
+        assert_eq!(bfs_cost([1], |_| [], |n| *n == 2), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra([1], line_successors, |n| *n == 10);
+        assert_eq!(result, Some((vec![1, 2, 3, 4, 5, 10], 7)));
+
+        let result = dijkstra([1, 5], line_successors, |n| *n == 10);
+        assert_eq!(result, Some((vec![5, 10], 3)));
+
+        // the start itself can be the goal.
+        assert_eq!(
+            dijkstra([3], line_successors, |n| *n == 3),
+            Some((vec![3], 0))
+        );
+        let bounded = |n: &u32| line_successors(n).into_iter().filter(|(n, _)| *n <= 20);
+        assert_eq!(dijkstra([11], bounded, |n| *n == 10), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        // a 5x5 grid with a wall at x = 2, except for the bottom row.
+        let successors = |&(x, y): &(i32, i32)| {
+            [(0, 1), (1, 0), (0, -1), (-1, 0)]
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .into_iter()
+                .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+                .filter(|&(x, y)| x != 2 || y == 4)
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+
+        let (path, cost) = astar([(0, 0)], successors, manhattan, |n| *n == goal).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.contains(&(2, 4)));
+
+        let (_, dijkstra_cost) = dijkstra([(0, 0)], successors, |n| *n == goal).unwrap();
+        assert_eq!(dijkstra_cost, cost);
+    }
+}
@@ -0,0 +1,327 @@
+/*
+ * An unbounded 2D grid that only stores the cells that were set.
+ * The bounding box of those cells is kept up to date as they are inserted,
+ * and recomputed on demand after a removal on its edge.
+ */
+use super::Point2;
+use rustc_hash::FxHashMap;
+use std::cell::Cell;
+use std::fmt::{self, Write};
+use std::hash::Hash;
+
+/// A position type usable as a key, implemented for `(x, y)` tuples and `Point2<i32>`.
+pub trait GridPos: Copy + Eq + Hash {
+    fn x(&self) -> i32;
+    fn y(&self) -> i32;
+    fn from_xy(x: i32, y: i32) -> Self;
+}
+
+impl GridPos for (i32, i32) {
+    fn x(&self) -> i32 {
+        self.0
+    }
+
+    fn y(&self) -> i32 {
+        self.1
+    }
+
+    fn from_xy(x: i32, y: i32) -> Self {
+        (x, y)
+    }
+}
+
+impl GridPos for Point2<i32> {
+    fn x(&self) -> i32 {
+        self.x
+    }
+
+    fn y(&self) -> i32 {
+        self.y
+    }
+
+    fn from_xy(x: i32, y: i32) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+/// An inclusive rectangle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+impl Bounds {
+    fn point(x: i32, y: i32) -> Self {
+        Bounds {
+            min_x: x,
+            max_x: x,
+            min_y: y,
+            max_y: y,
+        }
+    }
+
+    fn including(self, x: i32, y: i32) -> Self {
+        Bounds {
+            min_x: self.min_x.min(x),
+            max_x: self.max_x.max(x),
+            min_y: self.min_y.min(y),
+            max_y: self.max_y.max(y),
+        }
+    }
+
+    fn on_edge(&self, x: i32, y: i32) -> bool {
+        x == self.min_x || x == self.max_x || y == self.min_y || y == self.max_y
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max_x - self.min_x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max_y - self.min_y + 1) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y)
+    }
+
+    /// Grown by `padding` cells on every side.
+    pub fn padded(&self, padding: i32) -> Self {
+        Bounds {
+            min_x: self.min_x - padding,
+            max_x: self.max_x + padding,
+            min_y: self.min_y - padding,
+            max_y: self.max_y + padding,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T, P = (i32, i32)> {
+    cells: FxHashMap<P, T>,
+    bounds: Cell<Option<Bounds>>,
+    // set when a removal may have shrunk the bounds.
+    stale: Cell<bool>,
+}
+
+impl<T, P: GridPos> Default for SparseGrid<T, P> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: FxHashMap::default(),
+            bounds: Cell::new(None),
+            stale: Cell::new(false),
+        }
+    }
+}
+
+impl<T, P: GridPos> SparseGrid<T, P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, pos: P, value: T) -> Option<T> {
+        if !self.stale.get() {
+            let bounds = match self.bounds.get() {
+                Some(bounds) => bounds.including(pos.x(), pos.y()),
+                None => Bounds::point(pos.x(), pos.y()),
+            };
+            self.bounds.set(Some(bounds));
+        }
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: &P) -> Option<T> {
+        let removed = self.cells.remove(pos);
+        if removed.is_some() {
+            if let Some(bounds) = self.bounds.get() {
+                if bounds.on_edge(pos.x(), pos.y()) {
+                    self.stale.set(true);
+                }
+            }
+        }
+        removed
+    }
+
+    pub fn get(&self, pos: &P) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: &P) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    pub fn contains(&self, pos: &P) -> bool {
+        self.cells.contains_key(pos)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&P, &T)> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &P> {
+        self.cells.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// The smallest rectangle containing every cell, `None` when empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        if self.stale.get() {
+            let bounds = self.cells.keys().fold(None, |acc: Option<Bounds>, pos| {
+                Some(match acc {
+                    Some(bounds) => bounds.including(pos.x(), pos.y()),
+                    None => Bounds::point(pos.x(), pos.y()),
+                })
+            });
+            self.bounds.set(bounds);
+            self.stale.set(false);
+        }
+        self.bounds.get()
+    }
+
+    /// Number of unset cells inside the bounds.
+    pub fn count_empty(&self) -> usize {
+        self.bounds().map_or(0, |b| b.area() - self.len())
+    }
+
+    /// Renders the bounds grown by `padding`, calling `cell` for every position.
+    pub fn render<R: fmt::Display>(
+        &self,
+        padding: i32,
+        cell: impl FnMut(P, Option<&T>) -> R,
+    ) -> String {
+        match self.bounds() {
+            Some(bounds) => self.render_in(bounds.padded(padding), cell),
+            None => String::new(),
+        }
+    }
+
+    /// Renders any area, one line per row.
+    pub fn render_in<R: fmt::Display>(
+        &self,
+        area: Bounds,
+        mut cell: impl FnMut(P, Option<&T>) -> R,
+    ) -> String {
+        let mut result = String::new();
+        for y in area.min_y..=area.max_y {
+            if y > area.min_y {
+                result.push('\n');
+            }
+            for x in area.min_x..=area.max_x {
+                let pos = P::from_xy(x, y);
+                write!(result, "{}", cell(pos, self.get(&pos))).unwrap();
+            }
+        }
+        result
+    }
+}
+
+impl<T, P: GridPos> FromIterator<(P, T)> for SparseGrid<T, P> {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Self {
+        let mut result = SparseGrid::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<T, P: GridPos> Extend<(P, T)> for SparseGrid<T, P> {
+    fn extend<I: IntoIterator<Item = (P, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+impl<T: PartialEq, P: GridPos> PartialEq for SparseGrid<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert((2, 3), '#');
+        grid.insert((-1, 5), '#');
+        grid.insert((0, 4), '#');
+        let expected = Bounds {
+            min_x: -1,
+            max_x: 2,
+            min_y: 3,
+            max_y: 5,
+        };
+        assert_eq!(grid.bounds(), Some(expected));
+        assert_eq!(expected.area(), 12);
+
+        // removing an inner cell keeps the bounds, removing an edge shrinks them.
+        grid.remove(&(0, 4));
+        assert_eq!(grid.bounds(), Some(expected));
+        grid.remove(&(-1, 5));
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min_x: 2,
+                max_x: 2,
+                min_y: 3,
+                max_y: 3,
+            })
+        );
+
+        grid.remove(&(2, 3));
+        assert_eq!(grid.bounds(), None);
+        grid.insert((7, 7), '#');
+        assert_eq!(grid.bounds(), Some(Bounds::point(7, 7)));
+    }
+
+    #[test]
+    fn test_count_empty() {
+        let grid: SparseGrid<()> = [(0, 0), (2, 0), (1, 1)]
+            .map(|p| (p, ()))
+            .into_iter()
+            .collect();
+        assert_eq!(grid.count_empty(), 3);
+        assert_eq!(SparseGrid::<()>::new().count_empty(), 0);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: SparseGrid<u32> = [((0, 0), 1), ((2, 1), 2)].into_iter().collect();
+        assert_eq!(
+            grid.render(0, |_, v| v
+                .map_or('.', |v| char::from_digit(*v, 10).unwrap())),
+            "1..\n..2"
+        );
+        assert_eq!(
+            grid.render(1, |_, v| if v.is_some() { '#' } else { '.' }),
+            ".....\n.#...\n...#.\n....."
+        );
+        assert_eq!(
+            grid.render_in(Bounds::point(5, 5), |(x, y), _| format!("{},{}", x, y)),
+            "5,5"
+        );
+        assert_eq!(SparseGrid::<u32>::new().render(2, |_, _| '.'), "");
+    }
+}
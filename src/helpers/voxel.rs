@@ -0,0 +1,362 @@
+/*
+ * Sets of unit cubes in 3D, such as lava droplets, at any coordinates including negative ones.
+ * A voxel at `(x, y, z)` fills the cube from that corner to `(x + 1, y + 1, z + 1)`.
+ * The exterior is the air reachable from outside the bounding box, while air pockets are
+ * the air left enclosed. Surfaces export to Wavefront OBJ or ASCII STL, for a 3D viewer.
+ */
+use super::Point3;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::Write;
+
+pub type Voxel = Point3<i32>;
+
+/// The smallest box holding some voxels, both corners included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds3 {
+    pub min: Voxel,
+    pub max: Voxel,
+}
+
+impl Bounds3 {
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        (self.min.x..=self.max.x).contains(&voxel.x)
+            && (self.min.y..=self.max.y).contains(&voxel.y)
+            && (self.min.z..=self.max.z).contains(&voxel.z)
+    }
+
+    /// The box extended by `margin` on every side.
+    pub fn grow(&self, margin: i32) -> Self {
+        let offset = Voxel::new(margin, margin, margin);
+        Bounds3 {
+            min: self.min - offset,
+            max: self.max + offset,
+        }
+    }
+
+    pub fn voxels(&self) -> impl Iterator<Item = Voxel> + '_ {
+        (self.min.x..=self.max.x).flat_map(move |x| {
+            (self.min.y..=self.max.y)
+                .flat_map(move |y| (self.min.z..=self.max.z).map(move |z| Voxel::new(x, y, z)))
+        })
+    }
+}
+
+/// The side of a voxel facing `normal`, one of the six unit vectors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Face {
+    pub voxel: Voxel,
+    pub normal: Voxel,
+}
+
+impl Face {
+    /// The corners of the face, counter-clockwise when looking at it from outside.
+    pub fn corners(&self) -> [Voxel; 4] {
+        let (x, y, z) = (
+            Voxel::new(1, 0, 0),
+            Voxel::new(0, 1, 0),
+            Voxel::new(0, 0, 1),
+        );
+        let n = self.normal;
+        // two edges whose cross product points along the normal.
+        let (u, w) = match (n.x, n.y, n.z) {
+            (1, 0, 0) => (y, z),
+            (-1, 0, 0) => (z, y),
+            (0, 1, 0) => (z, x),
+            (0, -1, 0) => (x, z),
+            (0, 0, 1) => (x, y),
+            (0, 0, -1) => (y, x),
+            _ => panic!("{} is not a unit vector along an axis", n),
+        };
+        let base = self.voxel + Voxel::new(n.x.max(0), n.y.max(0), n.z.max(0));
+        [base, base + u, base + u + w, base + w]
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Volume {
+    voxels: FxHashSet<Voxel>,
+}
+
+impl Volume {
+    pub fn new() -> Self {
+        Volume {
+            voxels: FxHashSet::default(),
+        }
+    }
+
+    /// Returns whether the voxel was new.
+    pub fn insert(&mut self, voxel: Voxel) -> bool {
+        self.voxels.insert(voxel)
+    }
+
+    pub fn contains(&self, voxel: Voxel) -> bool {
+        self.voxels.contains(&voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.voxels.iter().copied()
+    }
+
+    /// `None` for an empty volume.
+    pub fn bounds(&self) -> Option<Bounds3> {
+        let mut voxels = self.iter();
+        let first = voxels.next()?;
+        Some(voxels.fold(
+            Bounds3 {
+                min: first,
+                max: first,
+            },
+            |b, v| Bounds3 {
+                min: Voxel::new(b.min.x.min(v.x), b.min.y.min(v.y), b.min.z.min(v.z)),
+                max: Voxel::new(b.max.x.max(v.x), b.max.y.max(v.y), b.max.z.max(v.z)),
+            },
+        ))
+    }
+
+    /// The faces not shared with another voxel, inside air pockets included.
+    pub fn faces(&self) -> Vec<Face> {
+        self.faces_towards(|voxel| !self.contains(voxel))
+    }
+
+    /// The faces that can be reached from outside.
+    pub fn exterior_faces(&self) -> Vec<Face> {
+        let exterior = self.exterior();
+        self.faces_towards(|voxel| exterior.contains(&voxel))
+    }
+
+    pub fn surface_area(&self) -> usize {
+        self.faces().len()
+    }
+
+    pub fn exterior_surface_area(&self) -> usize {
+        self.exterior_faces().len()
+    }
+
+    /// The area of the faces around air pockets.
+    pub fn interior_surface_area(&self) -> usize {
+        self.surface_area() - self.exterior_surface_area()
+    }
+
+    /// The air reachable from outside, within one voxel around the bounds.
+    pub fn exterior(&self) -> FxHashSet<Voxel> {
+        let Some(bounds) = self.bounds() else {
+            return FxHashSet::default();
+        };
+        let area = bounds.grow(1);
+        flood(area.min, |voxel| {
+            area.contains(voxel) && !self.contains(voxel)
+        })
+    }
+
+    /// The pockets of air that can't be reached from outside, each one as a volume.
+    pub fn air_pockets(&self) -> Vec<Volume> {
+        let Some(bounds) = self.bounds() else {
+            return vec![];
+        };
+        let exterior = self.exterior();
+        let enclosed: FxHashSet<Voxel> = bounds
+            .voxels()
+            .filter(|voxel| !self.contains(*voxel) && !exterior.contains(voxel))
+            .collect();
+        components(&enclosed)
+    }
+
+    /// The groups of voxels connected through their faces.
+    pub fn components(&self) -> Vec<Volume> {
+        components(&self.voxels)
+    }
+
+    /// The faces as a Wavefront OBJ mesh, one quad per face.
+    pub fn to_obj(faces: &[Face]) -> String {
+        let mut vertices: FxHashMap<Voxel, usize> = FxHashMap::default();
+        let mut result = String::new();
+        let mut quads = String::new();
+
+        for face in faces {
+            let mut ids = vec![];
+            for corner in face.corners() {
+                let next_id = vertices.len() + 1;
+                let id = *vertices.entry(corner).or_insert_with(|| {
+                    writeln!(result, "v {} {} {}", corner.x, corner.y, corner.z).unwrap();
+                    next_id
+                });
+                ids.push(id.to_string());
+            }
+            writeln!(quads, "f {}", ids.join(" ")).unwrap();
+        }
+
+        result + &quads
+    }
+
+    /// The faces as an ASCII STL solid called `name`, two triangles per face.
+    pub fn to_stl(name: &str, faces: &[Face]) -> String {
+        let mut result = format!("solid {}\n", name);
+
+        for face in faces {
+            let [a, b, c, d] = face.corners();
+            for triangle in [[a, b, c], [a, c, d]] {
+                let n = face.normal;
+                writeln!(result, "  facet normal {} {} {}", n.x, n.y, n.z).unwrap();
+                writeln!(result, "    outer loop").unwrap();
+                for v in triangle {
+                    writeln!(result, "      vertex {} {} {}", v.x, v.y, v.z).unwrap();
+                }
+                writeln!(result, "    endloop").unwrap();
+                writeln!(result, "  endfacet").unwrap();
+            }
+        }
+
+        writeln!(result, "endsolid {}", name).unwrap();
+        result
+    }
+
+    // the faces whose neighbour on the other side is accepted by `facing`.
+    fn faces_towards(&self, facing: impl Fn(Voxel) -> bool) -> Vec<Face> {
+        let mut result = vec![];
+        for voxel in self.iter() {
+            for neighbour in voxel.neighbours() {
+                if facing(neighbour) {
+                    result.push(Face {
+                        voxel,
+                        normal: neighbour - voxel,
+                    });
+                }
+            }
+        }
+        result
+    }
+}
+
+impl FromIterator<Voxel> for Volume {
+    fn from_iter<I: IntoIterator<Item = Voxel>>(iter: I) -> Self {
+        Volume {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+// the voxels reachable from `start` through those accepted by `open`.
+fn flood(start: Voxel, open: impl Fn(Voxel) -> bool) -> FxHashSet<Voxel> {
+    let mut visited = FxHashSet::default();
+    let mut to_visit = vec![start];
+
+    while let Some(current) = to_visit.pop() {
+        if !visited.insert(current) {
+            continue;
+        }
+        for neighbour in current.neighbours() {
+            if open(neighbour) && !visited.contains(&neighbour) {
+                to_visit.push(neighbour);
+            }
+        }
+    }
+
+    visited
+}
+
+fn components(voxels: &FxHashSet<Voxel>) -> Vec<Volume> {
+    let mut seen: FxHashSet<Voxel> = FxHashSet::default();
+    let mut result = vec![];
+
+    for voxel in voxels {
+        if seen.contains(voxel) {
+            continue;
+        }
+        let component = flood(*voxel, |v| voxels.contains(&v));
+        seen.extend(component.iter().copied());
+        result.push(Volume { voxels: component });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 3×3×3 cube with its center missing, around `corner`.
+    fn hollow(corner: Voxel) -> Volume {
+        let bounds = Bounds3 {
+            min: corner,
+            max: corner + Voxel::new(2, 2, 2),
+        };
+        let center = corner + Voxel::new(1, 1, 1);
+        bounds.voxels().filter(|v| *v != center).collect()
+    }
+
+    #[test]
+    fn test_surfaces() {
+        let pair: Volume = [Voxel::new(1, 1, 1), Voxel::new(2, 1, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(pair.surface_area(), 10);
+        assert_eq!(pair.exterior_surface_area(), 10);
+
+        for corner in [Voxel::new(0, 0, 0), Voxel::new(-7, -3, -12)] {
+            let shell = hollow(corner);
+            assert_eq!(shell.surface_area(), 60);
+            assert_eq!(shell.exterior_surface_area(), 54);
+            assert_eq!(shell.interior_surface_area(), 6);
+
+            let pockets = shell.air_pockets();
+            assert_eq!(pockets.len(), 1);
+            assert!(pockets[0].contains(corner + Voxel::new(1, 1, 1)));
+        }
+
+        assert_eq!(Volume::new().exterior_surface_area(), 0);
+    }
+
+    #[test]
+    fn test_components() {
+        let mut volume = hollow(Voxel::new(0, 0, 0));
+        volume.insert(Voxel::new(10, 0, 0));
+        volume.insert(Voxel::new(-5, -5, -5));
+        let mut sizes: Vec<usize> = volume.components().iter().map(|c| c.len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 26]);
+        assert_eq!(
+            volume.bounds(),
+            Some(Bounds3 {
+                min: Voxel::new(-5, -5, -5),
+                max: Voxel::new(10, 2, 2)
+            })
+        );
+    }
+
+    #[test]
+    fn test_face_orientation() {
+        let cube: Volume = [Voxel::new(-1, 0, 2)].into_iter().collect();
+        for face in cube.faces() {
+            let [a, b, c, _] = face.corners();
+            let (e1, e2) = (b - a, c - b);
+            let cross = Voxel::new(
+                e1.y * e2.z - e1.z * e2.y,
+                e1.z * e2.x - e1.x * e2.z,
+                e1.x * e2.y - e1.y * e2.x,
+            );
+            assert_eq!(cross, face.normal);
+        }
+    }
+
+    #[test]
+    fn test_export() {
+        let cube: Volume = [Voxel::new(0, 0, 0)].into_iter().collect();
+        let obj = Volume::to_obj(&cube.faces());
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 8);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 6);
+
+        let stl = Volume::to_stl("cube", &cube.exterior_faces());
+        assert!(stl.starts_with("solid cube\n"));
+        assert!(stl.ends_with("endsolid cube\n"));
+        assert_eq!(stl.matches("facet normal").count(), 12);
+        assert_eq!(stl.matches("vertex").count(), 36);
+    }
+}
@@ -5,7 +5,14 @@
  */
 use std::env;
 use std::fs;
-
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub mod alloc_stats;
+pub mod cache;
+pub mod differential;
+#[cfg(feature = "generators")]
+pub mod generators;
 pub mod helpers;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -15,14 +22,26 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
+        use advent_of_code::alloc_stats;
         use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
         use std::fmt::Display;
         use std::time::Instant;
 
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
+        fn print_result<T: Display>(part: u8, func: impl FnOnce(&str) -> Option<T>, input: &str) {
             let timer = Instant::now();
-            let result = func(input);
+            let (result, stats) = alloc_stats::measure(|| func(input));
             let elapsed = timer.elapsed();
+
+            if advent_of_code::is_json_output() {
+                let answer = result.map(|result| result.to_string());
+                println!(
+                    "{}",
+                    advent_of_code::json_report(part, answer, elapsed, stats)
+                );
+                return;
+            }
+
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, part, ANSI_RESET);
             match result {
                 Some(result) => {
                     println!(
@@ -34,10 +53,31 @@ macro_rules! solve {
                     println!("not solved.")
                 }
             }
+            if let Some(stats) = stats {
+                println!(
+                    "{}memory: {} allocations, {} allocated, {} peak{}",
+                    ANSI_ITALIC,
+                    stats.allocations,
+                    alloc_stats::format_bytes(stats.bytes),
+                    alloc_stats::format_bytes(stats.peak_bytes),
+                    ANSI_RESET
+                );
+            }
         }
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        print_result($part, $solver, $input);
+    }};
+}
+
+/// Runs `variants` of a part side by side when `--compare` was passed, see `differential.rs`.
+#[macro_export]
+macro_rules! compare {
+    ($part:expr, $variants:expr, $input:expr) => {{
+        if advent_of_code::differential::is_requested()
+            && !advent_of_code::differential::print_report($part, $variants, $input)
+        {
+            std::process::exit(1);
+        }
     }};
 }
 
@@ -50,6 +90,71 @@ pub fn read_file(folder: &str, day: u8) -> String {
     f.expect("could not open input file")
 }
 
+/// Reads the puzzle input for `day`, from `<dir>/{day}.txt` if `--inputs <dir>` was passed.
+/// example: `cargo solve 20 --release -- --inputs src/generated`
+pub fn read_input(day: u8) -> String {
+    let mut args = pico_args::Arguments::from_env();
+    let dir: Option<String> = args
+        .opt_value_from_str("--inputs")
+        .expect("--inputs should be a path");
+
+    let filepath = input_path(dir.as_deref(), day);
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|_| panic!("could not open input file {}", filepath.display()))
+}
+
+/// The file `read_input` reads for `day`, given the directory passed to `--inputs` if any.
+pub fn input_path(dir: Option<&str>, day: u8) -> PathBuf {
+    let file = format!("{:02}.txt", day);
+    match dir {
+        Some(dir) => Path::new(dir).join(file),
+        None => env::current_dir()
+            .unwrap()
+            .join("src")
+            .join("inputs")
+            .join(file),
+    }
+}
+
+/// Whether `--json` was passed on the command line: `solve!` then prints one JSON object per part.
+pub fn is_json_output() -> bool {
+    pico_args::Arguments::from_env().contains("--json")
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// A part's result as a JSON object, memory fields are `null` without the `alloc-stats` feature.
+pub fn json_report(
+    part: u8,
+    answer: Option<String>,
+    elapsed: Duration,
+    stats: Option<alloc_stats::AllocStats>,
+) -> String {
+    let null = || "null".to_string();
+    format!(
+        "{{\"part\":{},\"answer\":{},\"elapsed_ns\":{},\"allocations\":{},\"bytes\":{},\"peak_bytes\":{}}}",
+        part,
+        answer.as_deref().map_or_else(null, json_string),
+        elapsed.as_nanos(),
+        stats.map_or_else(null, |s| s.allocations.to_string()),
+        stats.map_or_else(null, |s| s.bytes.to_string()),
+        stats.map_or_else(null, |s| s.peak_bytes.to_string()),
+    )
+}
+
 fn parse_time(val: &str, postfix: &str) -> f64 {
     val.split(postfix).next().unwrap().parse().unwrap()
 }
@@ -122,4 +227,28 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_json_report() {
+        assert_eq!(
+            json_report(1, Some("42".into()), Duration::from_micros(3), None),
+            r#"{"part":1,"answer":"42","elapsed_ns":3000,"allocations":null,"bytes":null,"peak_bytes":null}"#
+        );
+
+        let stats = alloc_stats::AllocStats {
+            allocations: 2,
+            bytes: 64,
+            peak_bytes: 32,
+        };
+        assert_eq!(
+            json_report(2, None, Duration::from_nanos(5), Some(stats)),
+            r#"{"part":2,"answer":null,"elapsed_ns":5,"allocations":2,"bytes":64,"peak_bytes":32}"#
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("#..\n.\"#\\"), r##""#..\n.\"#\\""##);
+        assert_eq!(json_string("\t"), r#""\u0009""#);
+    }
 }
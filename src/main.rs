@@ -2,24 +2,80 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use advent_of_code::{alloc_stats, cache, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::env;
 use std::process::Command;
 
+const PROFILE: &str = "release";
+
 fn main() {
+    // `--inputs <dir>` is forwarded to every day, e.g. to run against `cargo gen` output.
+    let mut args = pico_args::Arguments::from_env();
+    let inputs: Option<String> = args
+        .opt_value_from_str("--inputs")
+        .expect("--inputs should be a path");
+
+    // answers are cached when `AOC_CACHE` is set, `--no-cache` recomputes (and refreshes) them.
+    let use_cache = env::var_os("AOC_CACHE").is_some();
+    let refresh = args.contains("--no-cache");
+    let root = env::current_dir().unwrap();
+    // the features each day is built with, part of the cache key as they change the output.
+    let features: Vec<&str> = if alloc_stats::is_enabled() {
+        vec!["alloc-stats"]
+    } else {
+        vec![]
+    };
+    let features_arg = features.join(",");
+
     let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+        .map(|day_num| {
+            let day = format!("{:02}", day_num);
 
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
+            let input_path = advent_of_code::input_path(inputs.as_deref(), day_num);
+            let key = if use_cache {
+                cache::key(&root, day_num, &input_path, PROFILE, &features)
+            } else {
+                None
+            };
+            let cached = match key {
+                Some(key) if !refresh => cache::load(&root, day_num, key),
+                _ => None,
+            };
 
             println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
+            println!(
+                "{}| Day {} |{}{}",
+                ANSI_BOLD,
+                day,
+                ANSI_RESET,
+                if cached.is_some() { " (cached)" } else { "" }
+            );
             println!("----------");
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
+            let output = match cached {
+                Some(output) => output,
+                None => {
+                    let mut cmd_args = vec!["run", "--release", "--bin", &day];
+                    if !features.is_empty() {
+                        cmd_args.extend(["--features", &features_arg]);
+                    }
+                    if let Some(dir) = &inputs {
+                        cmd_args.extend(["--", "--inputs", dir]);
+                    }
+
+                    let cmd = Command::new("cargo").args(cmd_args).output().unwrap();
+                    let output = String::from_utf8(cmd.stdout).unwrap();
+
+                    if let Some(key) = key {
+                        if cmd.status.success() && !output.is_empty() {
+                            if let Err(e) = cache::store(&root, day_num, key, &output) {
+                                eprintln!("Failed to cache day {}: {}", day, e);
+                            }
+                        }
+                    }
+                    output
+                }
+            };
             let is_empty = output.is_empty();
 
             println!(